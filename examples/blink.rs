@@ -31,17 +31,17 @@ fn main() -> ! {
 
     // Calling this enables HSI and sets it as the system clock.
     // TODO: Implement clock configuration
-    let _clocks = rcc.cfgr.freeze(&mut flash.acr);
+    let clocks = rcc.cfgr.freeze(&mut flash.acr);
 
     // PK3 is connected to the LCD backlight
     let mut gpiok = dp.GPIOK.split(&mut rcc.ahb1);
     let mut led = gpiok.pk3.into_push_pull_output(&mut gpiok.moder, &mut gpiok.otyper);
 
-    let mut timer = Delay::new(cp.SYST);
+    let mut timer = Delay::new(cp.SYST, clocks);
     loop {
-        led.set_high();
+        led.set_high().unwrap();
         timer.delay_ms(1000_u32);
-        led.set_low();
+        led.set_low().unwrap();
         timer.delay_ms(1000_u32);
     }
 }
@@ -4,8 +4,24 @@ MEMORY
     /* TODO Adjust these memory regions to match your device memory layout */
     FLASH : ORIGIN = 0x08000000, LENGTH = 1024K 
     RAM : ORIGIN = 0x20000000, LENGTH = 320K
+    ITCM : ORIGIN = 0x00000000, LENGTH = 16K
 }
 
+/*  Code executed from ITCM RAM, e.g. the flash operations that run while the flash is busy.
+    The section is copied from flash by `stm32f7xx_hal::flash::init_itcm` */
+SECTIONS
+{
+    .itcm_text : ALIGN(4)
+    {
+        __sitcm_text = .;
+        *(.itcm_text .itcm_text.*);
+        . = ALIGN(4);
+        __eitcm_text = .;
+    } > ITCM AT > FLASH
+
+    __siitcm_text = LOADADDR(.itcm_text);
+} INSERT AFTER .rodata;
+
 /*  This is where the call stack will be allocated.
     The stack is of the full descending type.
     You may want to use this variable to locate the call stack and static
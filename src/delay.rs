@@ -1,17 +1,55 @@
+//! Delays
+
+use core::cmp;
+
 use crate::hal::blocking::delay::{DelayMs, DelayUs};
+use crate::rcc::Clocks;
 use cast::u32;
 use cortex_m::peripheral::syst::SystClkSource;
 use cortex_m::peripheral::SYST;
 
+/// Largest value of the 24-bit SysTick reload register
+const MAX_RVR: u32 = 0x00FF_FFFF;
+
+/// System timer (SysTick) as a delay provider
 pub struct Delay {
+    clocks: Clocks,
     syst: SYST,
 }
 
 impl Delay {
-    pub fn new(mut syst: SYST) -> Self {
+    /// Configures the system timer (SysTick) as a delay provider
+    pub fn new(mut syst: SYST, clocks: Clocks) -> Self {
         syst.set_clock_source(SystClkSource::Core);
 
-        Delay { syst }
+        Delay { clocks, syst }
+    }
+
+    /// Releases the system timer (SysTick) resource
+    pub fn free(self) -> SYST {
+        self.syst
+    }
+
+    /// Waits for `ticks` core clock cycles, split in as many reload periods as needed
+    fn delay_ticks(&mut self, mut ticks: u64) {
+        while ticks != 0 {
+            let rvr = cmp::min(ticks, u64::from(MAX_RVR)) as u32;
+            ticks -= u64::from(rvr);
+
+            // A reload value of 0 stops the counter, a single cycle isn't worth waiting for
+            if rvr < 2 {
+                break;
+            }
+
+            // The counter wraps every RVR + 1 cycles
+            self.syst.set_reload(rvr - 1);
+            self.syst.clear_current();
+            self.syst.enable_counter();
+
+            while !self.syst.has_wrapped() {}
+
+            self.syst.disable_counter();
+        }
     }
 }
 
@@ -29,7 +67,8 @@ impl DelayMs<u16> for Delay {
 
 impl DelayMs<u32> for Delay {
     fn delay_ms(&mut self, ms: u32) {
-        self.delay_us(ms * 1_000);
+        let ticks = u64::from(ms) * u64::from(self.clocks.hclk().0 / 1_000);
+        self.delay_ticks(ticks);
     }
 }
 
@@ -47,15 +86,7 @@ impl DelayUs<u16> for Delay {
 
 impl DelayUs<u32> for Delay {
     fn delay_us(&mut self, us: u32) {
-        let sysclk = 16_000_000;
-        let rvr = us * (sysclk / 1_000_000);
-
-        self.syst.set_reload(rvr);
-        self.syst.clear_current();
-        self.syst.enable_counter();
-
-        while !self.syst.has_wrapped() {}
-
-        self.syst.disable_counter();
+        let ticks = u64::from(us) * u64::from(self.clocks.hclk().0 / 1_000_000);
+        self.delay_ticks(ticks);
     }
 }
@@ -0,0 +1,391 @@
+//! EEPROM emulation on the internal flash
+//!
+//! Values are stored as records appended to the active sector. A record is only considered
+//! valid once its trailing commit word has been programmed, so a power loss in the middle of a
+//! write leaves the previous value in place. When the active sector is full, the latest value of
+//! every key is copied to the next sector of the set, which then becomes active before the old
+//! sector is erased. Rotating through the sectors spreads the erase cycles over all of them.
+//! Erased sectors are invalidated in the data cache by `UnlockedFlash::erase_sector`, so a
+//! rotated sector never returns records from stale cache lines.
+//!
+//! Sector layout:
+//!
+//! - `MAGIC`, sequence number, inverted sequence number, active marker (one word each)
+//! - records
+//!
+//! Record layout:
+//!
+//! - key (bits 0..16) and length (bits 16..32)
+//! - inverted first word
+//! - data, padded to a word boundary
+//! - commit marker
+
+use core::ptr;
+
+use crate::flash::{self, Sector, UnlockedFlash};
+
+const MAGIC: u32 = 0x4545_5052;
+const HEADER_SIZE: usize = 16;
+const ERASED: u32 = 0xFFFF_FFFF;
+const COMMITTED: u32 = 0;
+const ACTIVE: u32 = 0;
+const TOMBSTONE: u16 = 0xFFFE;
+
+/// Largest value that can be stored
+pub const MAX_VALUE_LEN: usize = 0x8000;
+
+/// EEPROM emulation errors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// A flash operation failed
+    Flash(flash::Error),
+    /// Key `0xFFFF` is reserved
+    InvalidKey,
+    /// The value is longer than `MAX_VALUE_LEN`
+    ValueTooLarge,
+    /// The live values don't fit in a sector
+    Full,
+    /// At least two sectors are required
+    NotEnoughSectors,
+}
+
+impl From<flash::Error> for Error {
+    fn from(e: flash::Error) -> Self {
+        Error::Flash(e)
+    }
+}
+
+/// Record in the active sector
+#[derive(Clone, Copy)]
+struct Record {
+    address: usize,
+    key: u16,
+    len: u16,
+    committed: bool,
+}
+
+impl Record {
+    fn is_tombstone(&self) -> bool {
+        self.len == TOMBSTONE
+    }
+
+    fn data_len(&self) -> usize {
+        if self.is_tombstone() {
+            0
+        } else {
+            usize::from(self.len)
+        }
+    }
+
+    fn size(&self) -> usize {
+        record_size(self.data_len())
+    }
+
+    fn data(&self) -> &'static [u8] {
+        // NOTE(unsafe) the flash is memory mapped and the record lies within a sector
+        unsafe { core::slice::from_raw_parts((self.address + 8) as *const u8, self.data_len()) }
+    }
+}
+
+/// Iterator over the records of a sector
+struct Records {
+    address: usize,
+    end: usize,
+    corrupted: bool,
+}
+
+impl Iterator for Records {
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Record> {
+        if self.corrupted || self.address + 8 > self.end {
+            return None;
+        }
+
+        let header = read_word(self.address);
+        if header == ERASED {
+            return None;
+        }
+
+        let key = header as u16;
+        let len = (header >> 16) as u16;
+        let valid = read_word(self.address + 4) == !header
+            && (len == TOMBSTONE || usize::from(len) <= MAX_VALUE_LEN);
+
+        let record = Record {
+            address: self.address,
+            key,
+            len,
+            committed: false,
+        };
+        if !valid || self.address + record.size() > self.end {
+            // Interrupted header write, the rest of the sector can't be trusted
+            self.corrupted = true;
+            return None;
+        }
+
+        let committed = read_word(self.address + record.size() - 4) == COMMITTED;
+        self.address += record.size();
+
+        Some(Record {
+            committed,
+            ..record
+        })
+    }
+}
+
+/// Key-value store emulating an EEPROM on two or more flash sectors
+pub struct Eeprom<'a> {
+    sectors: &'a [Sector],
+    active: usize,
+    sequence: u32,
+    /// Address of the first free byte in the active sector
+    free: usize,
+}
+
+impl<'a> Eeprom<'a> {
+    /// Opens the store, formatting it if none of the sectors holds valid data
+    ///
+    /// This also finishes a compaction interrupted by a reset.
+    pub fn new(sectors: &'a [Sector], flash: &mut UnlockedFlash) -> Result<Self, Error> {
+        if sectors.len() < 2 {
+            return Err(Error::NotEnoughSectors);
+        }
+
+        let mut newest: Option<(usize, u32)> = None;
+        for (i, sector) in sectors.iter().enumerate() {
+            if let Some(sequence) = active_sequence(sector) {
+                newest = match newest {
+                    Some((_, s)) if (sequence.wrapping_sub(s) as i32) <= 0 => newest,
+                    _ => Some((i, sequence)),
+                };
+            }
+        }
+
+        let (active, sequence) = match newest {
+            Some(newest) => newest,
+            None => {
+                let sector = &sectors[0];
+                flash.erase_sector(sector)?;
+                write_header(flash, sector, 0)?;
+                flash.program(sector.address + 12, &ACTIVE.to_le_bytes())?;
+                (0, 0)
+            }
+        };
+
+        // Older sectors left active by an interrupted compaction
+        for (i, sector) in sectors.iter().enumerate() {
+            if i != active && active_sequence(sector).is_some() {
+                flash.erase_sector(sector)?;
+            }
+        }
+
+        let mut eeprom = Eeprom {
+            sectors,
+            active,
+            sequence,
+            free: 0,
+        };
+        eeprom.free = eeprom.find_free();
+
+        Ok(eeprom)
+    }
+
+    /// Reads the value stored under `key` into `buffer`
+    ///
+    /// Returns the length of the value, which is truncated if `buffer` is too short.
+    pub fn read(&self, key: u16, buffer: &mut [u8]) -> Option<usize> {
+        let record = self.find(key)?;
+        let data = record.data();
+        let len = data.len().min(buffer.len());
+        buffer[..len].copy_from_slice(&data[..len]);
+
+        Some(data.len())
+    }
+
+    /// Returns `true` if a value is stored under `key`
+    pub fn contains(&self, key: u16) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Stores `value` under `key`, replacing the previous value
+    pub fn write(
+        &mut self,
+        flash: &mut UnlockedFlash,
+        key: u16,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        if key == 0xFFFF {
+            return Err(Error::InvalidKey);
+        }
+        if value.len() > MAX_VALUE_LEN {
+            return Err(Error::ValueTooLarge);
+        }
+
+        self.append(flash, key, value.len() as u16, value)
+    }
+
+    /// Removes the value stored under `key`
+    pub fn remove(&mut self, flash: &mut UnlockedFlash, key: u16) -> Result<(), Error> {
+        if key == 0xFFFF {
+            return Err(Error::InvalidKey);
+        }
+        if !self.contains(key) {
+            return Ok(());
+        }
+
+        self.append(flash, key, TOMBSTONE, &[])
+    }
+
+    fn append(
+        &mut self,
+        flash: &mut UnlockedFlash,
+        key: u16,
+        len: u16,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        let sector = &self.sectors[self.active];
+        if self.free + record_size(value.len()) > sector.address + sector.size {
+            self.compact(flash)?;
+        }
+
+        let sector = &self.sectors[self.active];
+        if self.free + record_size(value.len()) > sector.address + sector.size {
+            return Err(Error::Full);
+        }
+
+        self.free = write_record(flash, self.free, key, len, value)?;
+
+        Ok(())
+    }
+
+    /// Moves the live values to the next sector
+    fn compact(&mut self, flash: &mut UnlockedFlash) -> Result<(), Error> {
+        let next = (self.active + 1) % self.sectors.len();
+        let target = &self.sectors[next];
+
+        let live = self.live_records().map(|r| r.size()).sum::<usize>();
+        if HEADER_SIZE + live > target.size {
+            return Err(Error::Full);
+        }
+
+        let sequence = self.sequence.wrapping_add(1);
+        flash.erase_sector(target)?;
+        write_header(flash, target, sequence)?;
+
+        let mut free = target.address + HEADER_SIZE;
+        for record in self.live_records() {
+            // NOTE(unsafe) copies a complete record of the active sector
+            let raw =
+                unsafe { core::slice::from_raw_parts(record.address as *const u8, record.size()) };
+            flash.program(free, raw)?;
+            free += record.size();
+        }
+
+        // Commit the new sector, then retire the old one
+        flash.program(target.address + 12, &ACTIVE.to_le_bytes())?;
+        let old = self.active;
+        self.active = next;
+        self.sequence = sequence;
+        self.free = free;
+
+        flash.erase_sector(&self.sectors[old])?;
+
+        Ok(())
+    }
+
+    fn records(&self) -> Records {
+        let sector = &self.sectors[self.active];
+        Records {
+            address: sector.address + HEADER_SIZE,
+            end: sector.address + sector.size,
+            corrupted: false,
+        }
+    }
+
+    /// Returns the latest committed record of `key`, unless it was removed
+    fn find(&self, key: u16) -> Option<Record> {
+        self.records()
+            .filter(|r| r.committed && r.key == key)
+            .last()
+            .filter(|r| !r.is_tombstone())
+    }
+
+    /// Returns the records holding the latest value of every key
+    fn live_records(&self) -> impl Iterator<Item = Record> + '_ {
+        self.records().filter(move |r| {
+            r.committed
+                && !r.is_tombstone()
+                && self
+                    .records()
+                    .skip_while(|o| o.address <= r.address)
+                    .all(|o| !(o.committed && o.key == r.key))
+        })
+    }
+
+    /// Returns the address of the first free byte of the active sector
+    fn find_free(&self) -> usize {
+        let mut records = self.records();
+        let mut free = records.address;
+        for record in &mut records {
+            free = record.address + record.size();
+        }
+
+        if records.corrupted {
+            // Force a compaction on the next write
+            let sector = &self.sectors[self.active];
+            sector.address + sector.size
+        } else {
+            free
+        }
+    }
+}
+
+/// Returns the sequence number of a sector holding an active store
+fn active_sequence(sector: &Sector) -> Option<u32> {
+    let sequence = read_word(sector.address + 4);
+    if read_word(sector.address) == MAGIC
+        && read_word(sector.address + 8) == !sequence
+        && read_word(sector.address + 12) == ACTIVE
+    {
+        Some(sequence)
+    } else {
+        None
+    }
+}
+
+fn write_header(flash: &mut UnlockedFlash, sector: &Sector, sequence: u32) -> Result<(), Error> {
+    flash.program(sector.address, &MAGIC.to_le_bytes())?;
+    flash.program(sector.address + 4, &sequence.to_le_bytes())?;
+    flash.program(sector.address + 8, &(!sequence).to_le_bytes())?;
+
+    Ok(())
+}
+
+/// Writes a record and returns the address following it
+fn write_record(
+    flash: &mut UnlockedFlash,
+    address: usize,
+    key: u16,
+    len: u16,
+    value: &[u8],
+) -> Result<usize, Error> {
+    let header = u32::from(key) | (u32::from(len) << 16);
+    let size = record_size(value.len());
+
+    flash.program(address, &header.to_le_bytes())?;
+    flash.program(address + 4, &(!header).to_le_bytes())?;
+    flash.program(address + 8, value)?;
+    flash.program(address + size - 4, &COMMITTED.to_le_bytes())?;
+
+    Ok(address + size)
+}
+
+fn record_size(len: usize) -> usize {
+    12 + ((len + 3) & !3)
+}
+
+fn read_word(address: usize) -> u32 {
+    // NOTE(unsafe) the flash is memory mapped
+    unsafe { ptr::read_volatile(address as *const u32) }
+}
@@ -0,0 +1,92 @@
+//! Dispatching of the shared EXTI interrupts
+//!
+//! EXTI lines 5 to 9 share the `EXTI9_5` interrupt and lines 10 to 15 share `EXTI15_10`. Register
+//! a handler per line and call the matching dispatch function from the interrupt handler:
+//!
+//! ``` ignore
+//! exti::register(13, on_button);
+//!
+//! #[interrupt]
+//! fn EXTI15_10() {
+//!     exti::dispatch_15_10();
+//! }
+//! ```
+
+use core::cell::Cell;
+
+use cortex_m::interrupt::{self, Mutex};
+
+use crate::stm32::EXTI;
+
+/// Number of EXTI lines connected to the GPIO pins
+pub const LINES: u8 = 16;
+
+/// Handler of an EXTI line
+pub type Handler = fn();
+
+// Only used as the initializer of `HANDLERS`
+#[allow(clippy::declare_interior_mutable_const)]
+const UNREGISTERED: Cell<Option<Handler>> = Cell::new(None);
+
+static HANDLERS: Mutex<[Cell<Option<Handler>>; LINES as usize]> =
+    Mutex::new([UNREGISTERED; LINES as usize]);
+
+/// Registers the handler of `line`, returning the previous one
+///
+/// # Panics
+///
+/// Panics if `line` is not a GPIO line (`0..16`)
+pub fn register(line: u8, handler: Handler) -> Option<Handler> {
+    assert!(line < LINES);
+
+    interrupt::free(|cs| HANDLERS.borrow(cs)[usize::from(line)].replace(Some(handler)))
+}
+
+/// Removes the handler of `line`, returning it
+///
+/// # Panics
+///
+/// Panics if `line` is not a GPIO line (`0..16`)
+pub fn unregister(line: u8) -> Option<Handler> {
+    assert!(line < LINES);
+
+    interrupt::free(|cs| HANDLERS.borrow(cs)[usize::from(line)].take())
+}
+
+/// Runs the handlers of the pending lines 5 to 9
+///
+/// Call this from the `EXTI9_5` interrupt handler.
+pub fn dispatch_9_5() {
+    dispatch(0b11_1110_0000)
+}
+
+/// Runs the handlers of the pending lines 10 to 15
+///
+/// Call this from the `EXTI15_10` interrupt handler.
+pub fn dispatch_15_10() {
+    dispatch(0b1111_1100_0000_0000)
+}
+
+/// Runs the handlers of the pending and unmasked lines in `lines`
+///
+/// The pending bit of a line is cleared before its handler runs, so an edge occurring meanwhile
+/// raises the interrupt again. Pending lines without a handler are cleared as well.
+pub fn dispatch(lines: u32) {
+    // NOTE(unsafe) atomic reads with no side effects
+    let exti = unsafe { &*EXTI::ptr() };
+    let pending = exti.pr.read().bits() & exti.imr.read().bits() & lines;
+
+    for line in 0..LINES {
+        if pending & (1 << line) == 0 {
+            continue;
+        }
+
+        // NOTE(unsafe) atomic write to a write-1-to-clear register
+        exti.pr.write(|w| unsafe { w.bits(1 << line) });
+
+        let handler = interrupt::free(|cs| HANDLERS.borrow(cs)[usize::from(line)].get());
+        if let Some(handler) = handler {
+            handler();
+        }
+    }
+}
@@ -123,3 +123,999 @@ impl SR {
         unsafe { &(*FLASH::ptr()).sr }
     }
 }
+
+/// Flash operation errors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// Programming sequence error
+    ProgrammingSequence,
+    /// Programming parallelism error
+    ProgrammingParallelism,
+    /// Programming alignment error
+    ProgrammingAlignment,
+    /// Write protection error
+    WriteProtection,
+    /// Operation error
+    Operation,
+    /// The unlock key sequence was rejected
+    Locked,
+    /// The address range is outside of the programmable memory
+    InvalidAddress,
+    /// The OTP block is locked
+    BlockLocked,
+    /// The flash is not in dual bank mode
+    SingleBank,
+    /// The running code is not executed from flash, so no bank is active
+    NoActiveBank,
+}
+
+const OPTKEY1: u32 = 0x0819_2A3B;
+const OPTKEY2: u32 = 0x4C5D_6E7F;
+
+const OPTCR_OPTLOCK: u32 = 1 << 0;
+const OPTCR_OPTSTRT: u32 = 1 << 1;
+
+/// `nWRP` bits of the single bank devices, one per sector
+const NWRP_MASK_F74X: u32 = 0xFF;
+/// Reserved `OPTCR` bits of the single bank devices, which keep their value
+const OPTCR_RESERVED_F74X: u32 = 0x3F00_0000;
+
+/// Returns the mask of the `nWRP` bits and of the reserved `OPTCR` bits
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3", feature = "stm32f7x6"))]
+fn optcr_masks() -> (u32, u32) {
+    (NWRP_MASK_F74X, OPTCR_RESERVED_F74X)
+}
+
+/// Returns the mask of the `nWRP` bits and of the reserved `OPTCR` bits
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+fn optcr_masks() -> (u32, u32) {
+    if is_f76x() {
+        (0xFFF, 0)
+    } else {
+        (NWRP_MASK_F74X, OPTCR_RESERVED_F74X)
+    }
+}
+
+const SR_BSY: u32 = 1 << 16;
+const SR_ERRORS: u32 = 0b1111_0010;
+
+impl SR {
+    /// Waits until the current flash operation has finished
+    pub(crate) fn wait(&mut self) -> Result<(), Error> {
+        while self.sr().read().bits() & SR_BSY != 0 {}
+
+        self.check_errors()
+    }
+
+    /// Checks and clears the error flags
+    fn check_errors(&mut self) -> Result<(), Error> {
+        let bits = self.sr().read().bits() & SR_ERRORS;
+        if bits == 0 {
+            return Ok(());
+        }
+
+        // Error flags are cleared by writing 1
+        self.sr().write(|w| unsafe { w.bits(bits) });
+
+        Err(if bits & (1 << 7) != 0 {
+            Error::ProgrammingSequence
+        } else if bits & (1 << 6) != 0 {
+            Error::ProgrammingParallelism
+        } else if bits & (1 << 5) != 0 {
+            Error::ProgrammingAlignment
+        } else if bits & (1 << 4) != 0 {
+            Error::WriteProtection
+        } else {
+            Error::Operation
+        })
+    }
+}
+
+/// Base address of the main flash memory (AXIM interface)
+pub const FLASH_BASE: usize = 0x0800_0000;
+
+const KEY1: u32 = 0x4567_0123;
+const KEY2: u32 = 0xCDEF_89AB;
+
+const CR_PG: u32 = 1 << 0;
+const CR_SER: u32 = 1 << 1;
+const CR_SNB_MASK: u32 = 0b1_1111 << 3;
+const CR_PSIZE_MASK: u32 = 0b11 << 8;
+const CR_STRT: u32 = 1 << 16;
+const CR_LOCK: u32 = 1 << 31;
+
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+const FLASH_SIZE_ADDRESS: usize = 0x1FF0_7A22;
+#[cfg(any(
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
+const FLASH_SIZE_ADDRESS: usize = 0x1FF0_F442;
+
+/// Returns the size of the main flash memory in bytes
+pub fn flash_size() -> usize {
+    // NOTE(unsafe) read-only factory programmed value
+    let kbytes = unsafe { core::ptr::read_volatile(FLASH_SIZE_ADDRESS as *const u16) };
+    usize::from(kbytes) * 1024
+}
+
+/// Flash sector
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sector {
+    /// Sector number
+    pub number: u8,
+    /// Start address
+    pub address: usize,
+    /// Size in bytes
+    pub size: usize,
+}
+
+impl Sector {
+    /// Returns the sector with the given number, if the device has it
+    pub fn new(number: u8) -> Option<Self> {
+        let (offset, size) = sector_layout(number)?;
+        if offset + size > flash_size() {
+            return None;
+        }
+
+        Some(Sector {
+            number,
+            address: FLASH_BASE + offset,
+            size,
+        })
+    }
+
+    /// Returns `true` if `address` lies within this sector
+    pub fn contains(&self, address: usize) -> bool {
+        address >= self.address && address < self.address + self.size
+    }
+
+    /// Value of the `SNB` field selecting this sector
+    fn snb(&self) -> u32 {
+        if self.number < 12 {
+            u32::from(self.number)
+        } else {
+            // Second bank sectors are selected by SNB[4]
+            0b1_0000 | u32::from(self.number - 12)
+        }
+    }
+}
+
+/// Returns the offset and size of a sector, for the largest flash size of the device family
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+fn sector_layout(number: u8) -> Option<(usize, usize)> {
+    const K: usize = 1024;
+    match number {
+        0..=3 => Some((usize::from(number) * 16 * K, 16 * K)),
+        4 => Some((64 * K, 64 * K)),
+        5..=7 => Some((usize::from(number - 4) * 128 * K, 128 * K)),
+        _ => None,
+    }
+}
+
+/// Returns the offset and size of a sector, for the largest flash size of the device family
+#[cfg(feature = "stm32f7x6")]
+fn sector_layout(number: u8) -> Option<(usize, usize)> {
+    const K: usize = 1024;
+    match number {
+        0..=3 => Some((usize::from(number) * 32 * K, 32 * K)),
+        4 => Some((128 * K, 128 * K)),
+        5..=7 => Some((usize::from(number - 4) * 256 * K, 256 * K)),
+        _ => None,
+    }
+}
+
+/// Returns the offset and size of a sector, for the largest flash size of the device family
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+fn sector_layout(number: u8) -> Option<(usize, usize)> {
+    const K: usize = 1024;
+    if is_dual_bank() {
+        // Each bank mirrors the single bank layout at half the sector size
+        let bank_size = flash_size() / 2;
+        let (bank, number) = if number < 12 {
+            (0, number)
+        } else {
+            (bank_size, number - 12)
+        };
+        let (offset, size) = match number {
+            0..=3 => (usize::from(number) * 16 * K, 16 * K),
+            4 => (64 * K, 64 * K),
+            5..=11 => (usize::from(number - 4) * 128 * K, 128 * K),
+            _ => return None,
+        };
+
+        // Banks of the 1 MB devices end after sector 7
+        if offset + size > bank_size {
+            return None;
+        }
+
+        Some((bank + offset, size))
+    } else {
+        match number {
+            0..=3 => Some((usize::from(number) * 32 * K, 32 * K)),
+            4 => Some((128 * K, 128 * K)),
+            5..=11 => Some((usize::from(number - 4) * 256 * K, 256 * K)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if the flash is currently organized as two banks
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+pub fn is_dual_bank() -> bool {
+    // NOTE(unsafe) atomic read with no side effects
+    is_f76x() && unsafe { (*FLASH::ptr()).optcr.read().bits() & (1 << 29) == 0 }
+}
+
+/// Returns `true` on STM32F76x/F77x devices, which support the dual bank mode
+///
+/// The `stm32f7x5` feature also covers the single bank STM32F745, whose `nDBANK` bit is reserved.
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+fn is_f76x() -> bool {
+    const DBGMCU_IDCODE: usize = 0xE004_2000;
+    const DEV_ID_F76X: u32 = 0x451;
+
+    // NOTE(unsafe) read-only device identifier
+    let idcode = unsafe { core::ptr::read_volatile(DBGMCU_IDCODE as *const u32) };
+    idcode & 0xFFF == DEV_ID_F76X
+}
+
+impl CR {
+    /// Unlocks the flash control register for erase and program operations
+    ///
+    /// The flash is locked again when the returned guard is dropped.
+    pub fn unlock<'a>(
+        &'a mut self,
+        keyr: &mut KEYR,
+        sr: &'a mut SR,
+    ) -> Result<UnlockedFlash<'a>, Error> {
+        if self.cr().read().bits() & CR_LOCK != 0 {
+            keyr.keyr().write(|w| unsafe { w.bits(KEY1) });
+            keyr.keyr().write(|w| unsafe { w.bits(KEY2) });
+
+            if self.cr().read().bits() & CR_LOCK != 0 {
+                return Err(Error::Locked);
+            }
+        }
+
+        Ok(UnlockedFlash { cr: self, sr })
+    }
+}
+
+/// Unlocked flash, able to erase and program the flash memory
+pub struct UnlockedFlash<'a> {
+    cr: &'a mut CR,
+    sr: &'a mut SR,
+}
+
+impl<'a> UnlockedFlash<'a> {
+    /// Erases a sector
+    ///
+    /// Instruction fetches from the same bank stall until the erase is complete. The erased range
+    /// is invalidated in the data cache, so the erased contents can be read back right away.
+    pub fn erase_sector(&mut self, sector: &Sector) -> Result<(), Error> {
+        self.sr.wait()?;
+
+        let snb = sector.snb() << 3;
+        self.cr.cr().modify(|r, w| unsafe {
+            w.bits((r.bits() & !(CR_SNB_MASK | CR_PSIZE_MASK | CR_PG)) | CR_SER | snb)
+        });
+        self.cr
+            .cr()
+            .modify(|r, w| unsafe { w.bits(r.bits() | CR_STRT) });
+        let result = self.sr.wait();
+        self.cr
+            .cr()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(CR_SER | CR_SNB_MASK)) });
+        invalidate_dcache(sector.address, sector.size);
+
+        result
+    }
+
+    /// Programs `data` starting at `address`
+    ///
+    /// Data is programmed with x8 parallelism, which is valid over the whole supply voltage
+    /// range. The target locations must have been erased.
+    pub fn program(&mut self, address: usize, data: &[u8]) -> Result<(), Error> {
+        if !is_programmable(address, data.len()) {
+            return Err(Error::InvalidAddress);
+        }
+
+        self.program_bytes(address, data)
+    }
+
+    /// Programs `data` into an OTP block, starting `offset` bytes into the block
+    ///
+    /// One-time programmable bytes can't be erased.
+    pub fn program_otp(&mut self, block: usize, offset: usize, data: &[u8]) -> Result<(), Error> {
+        if block >= OTP_BLOCKS || offset + data.len() > OTP_BLOCK_SIZE {
+            return Err(Error::InvalidAddress);
+        }
+        if is_otp_block_locked(block) {
+            return Err(Error::BlockLocked);
+        }
+
+        self.program_bytes(OTP_BASE + block * OTP_BLOCK_SIZE + offset, data)
+    }
+
+    /// Locks an OTP block, which can't be programmed anymore afterwards
+    pub fn lock_otp_block(&mut self, block: usize) -> Result<(), Error> {
+        if block >= OTP_BLOCKS {
+            return Err(Error::InvalidAddress);
+        }
+
+        self.program_bytes(OTP_LOCK_BASE + block, &[0x00])
+    }
+
+    fn program_bytes(&mut self, address: usize, data: &[u8]) -> Result<(), Error> {
+        self.sr.wait()?;
+
+        // PSIZE = x8
+        self.cr
+            .cr()
+            .modify(|r, w| unsafe { w.bits((r.bits() & !(CR_PSIZE_MASK | CR_SER)) | CR_PG) });
+
+        let mut result = Ok(());
+        for (i, byte) in data.iter().enumerate() {
+            // NOTE(unsafe) the address range was checked by the caller
+            unsafe { core::ptr::write_volatile((address + i) as *mut u8, *byte) };
+            cortex_m::asm::dsb();
+
+            result = self.sr.wait();
+            if result.is_err() {
+                break;
+            }
+        }
+
+        self.cr
+            .cr()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !CR_PG) });
+
+        result
+    }
+}
+
+impl<'a> Drop for UnlockedFlash<'a> {
+    fn drop(&mut self) {
+        self.cr
+            .cr()
+            .modify(|r, w| unsafe { w.bits(r.bits() | CR_LOCK) });
+    }
+}
+
+/// Interrupt handling while a flash operation executes from RAM
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterruptPolicy {
+    /// Interrupts stay enabled
+    ///
+    /// The vector table and every handler that may run must be located in RAM.
+    Enabled,
+    /// Interrupts are masked for the duration of the operation
+    Masked,
+    /// Only interrupts with a higher priority than this `BASEPRI` value are serviced
+    ///
+    /// The vector table and the handlers of those interrupts must be located in RAM.
+    Priority(u8),
+}
+
+impl<'a> UnlockedFlash<'a> {
+    /// Erases a sector, waiting for completion in a loop executed from RAM
+    ///
+    /// Unlike `erase_sector`, this doesn't stall the CPU while the sector is erased, so the
+    /// interrupts allowed by `policy` keep being serviced. The loop must first be copied to RAM
+    /// by `init_itcm`.
+    pub fn erase_sector_from_ram(
+        &mut self,
+        sector: &Sector,
+        policy: InterruptPolicy,
+    ) -> Result<(), Error> {
+        let snb = sector.snb() << 3;
+        let cr = self.cr.cr().read().bits() & !(CR_SNB_MASK | CR_PSIZE_MASK | CR_PG);
+
+        let result = self.run_from_ram(cr | CR_SER | snb, 0, &[], policy);
+        invalidate_dcache(sector.address, sector.size);
+
+        result
+    }
+
+    /// Programs `data` starting at `address`, from a loop executed from RAM
+    ///
+    /// See `erase_sector_from_ram`.
+    pub fn program_from_ram(
+        &mut self,
+        address: usize,
+        data: &[u8],
+        policy: InterruptPolicy,
+    ) -> Result<(), Error> {
+        if !is_programmable(address, data.len()) {
+            return Err(Error::InvalidAddress);
+        }
+
+        // PSIZE = x8
+        let cr = self.cr.cr().read().bits() & !(CR_PSIZE_MASK | CR_SER);
+
+        self.run_from_ram(cr | CR_PG, address, data, policy)
+    }
+
+    fn run_from_ram(
+        &mut self,
+        cr: u32,
+        address: usize,
+        data: &[u8],
+        policy: InterruptPolicy,
+    ) -> Result<(), Error> {
+        self.sr.wait()?;
+
+        let flash = FLASH::ptr() as usize;
+        let operation = || unsafe {
+            ram_operation(
+                (flash + 0x10) as *mut u32,
+                (flash + 0x0C) as *const u32,
+                cr,
+                address,
+                data.as_ptr() as usize,
+                data.len(),
+            )
+        };
+
+        match policy {
+            InterruptPolicy::Enabled => operation(),
+            InterruptPolicy::Masked => cortex_m::interrupt::free(|_| operation()),
+            InterruptPolicy::Priority(priority) => {
+                let basepri = cortex_m::register::basepri::read();
+                cortex_m::register::basepri_max::write(priority);
+                operation();
+                unsafe { cortex_m::register::basepri::write(basepri) };
+            }
+        }
+
+        self.cr
+            .cr()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(CR_PG | CR_SER | CR_SNB_MASK)) });
+
+        self.sr.wait()
+    }
+}
+
+/// Starts an erase (`len == 0`) or programs `len` bytes from `data` to `address`, and busy waits
+/// until the flash is idle
+///
+/// This function is placed in ITCM RAM by `init_itcm`. It's written in assembly so it never calls
+/// into code located in flash, whatever the optimization level.
+#[cfg(target_arch = "arm")]
+#[inline(never)]
+#[link_section = ".itcm_text.stm32f7xx_hal.ram_operation"]
+unsafe fn ram_operation(
+    cr: *mut u32,
+    sr: *const u32,
+    cr_bits: u32,
+    address: usize,
+    data: usize,
+    len: usize,
+) {
+    // 0x1_0000 is `CR_STRT` and `SR_BSY`, 0xF2 is `SR_ERRORS`
+    core::arch::asm!(
+        "str {cr_bits}, [{cr}]",
+        "cbnz {len}, 3f",
+        // Erase
+        "orr {tmp}, {cr_bits}, #0x10000",
+        "str {tmp}, [{cr}]",
+        "2:",
+        "ldr {tmp}, [{sr}]",
+        "tst {tmp}, #0x10000",
+        "bne 2b",
+        "b 5f",
+        // Program, further flash writes are stalled by the bus until the previous one completed
+        "3:",
+        "ldrb {tmp}, [{data}], #1",
+        "strb {tmp}, [{address}], #1",
+        "dsb sy",
+        "4:",
+        "ldr {tmp}, [{sr}]",
+        "tst {tmp}, #0x10000",
+        "bne 4b",
+        "tst {tmp}, #0xF2",
+        "bne 5f",
+        "subs {len}, {len}, #1",
+        "bne 3b",
+        "5:",
+        cr = in(reg) cr,
+        sr = in(reg) sr,
+        cr_bits = in(reg) cr_bits,
+        address = inout(reg) address => _,
+        data = inout(reg) data => _,
+        len = inout(reg) len => _,
+        tmp = out(reg) _,
+        options(nostack),
+    );
+}
+
+/// Flash operations can only run on the device
+#[cfg(not(target_arch = "arm"))]
+unsafe fn ram_operation(
+    _cr: *mut u32,
+    _sr: *const u32,
+    _cr_bits: u32,
+    _address: usize,
+    _data: usize,
+    _len: usize,
+) {
+    unimplemented!()
+}
+
+/// Returns the offset of `address` into the flash memory, through either the AXIM or the ITCM
+/// interface
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+fn flash_offset(address: usize) -> Option<usize> {
+    const ITCM_FLASH_BASE: usize = 0x0020_0000;
+
+    [FLASH_BASE, ITCM_FLASH_BASE]
+        .iter()
+        .find(|&&base| address >= base && address < base + flash_size())
+        .map(|base| address - base)
+}
+
+/// Copies the `.itcm_text` section from flash to ITCM RAM
+///
+/// The section is defined by this linker script fragment, also found in `memory.x`:
+///
+/// ```text
+/// MEMORY
+/// {
+///     ITCM : ORIGIN = 0x00000000, LENGTH = 16K
+/// }
+///
+/// SECTIONS
+/// {
+///     .itcm_text : ALIGN(4)
+///     {
+///         __sitcm_text = .;
+///         *(.itcm_text .itcm_text.*);
+///         . = ALIGN(4);
+///         __eitcm_text = .;
+///     } > ITCM AT > FLASH
+///
+///     __siitcm_text = LOADADDR(.itcm_text);
+/// } INSERT AFTER .rodata;
+/// ```
+///
+/// The data TCM at `0x2000_0000` can't be used, as the Cortex-M7 doesn't fetch instructions
+/// from it.
+///
+/// # Safety
+///
+/// The linker script must define the section as above. This must run before the first
+/// `*_from_ram` call, e.g. from a `#[pre_init]` function, while no code executes from ITCM RAM.
+pub unsafe fn init_itcm() {
+    extern "C" {
+        static __sitcm_text: u32;
+        static __eitcm_text: u32;
+        static __siitcm_text: u32;
+    }
+
+    let start = &__sitcm_text as *const u32 as usize;
+    let end = &__eitcm_text as *const u32 as usize;
+    let load = &__siitcm_text as *const u32 as usize;
+
+    for offset in (0..end - start).step_by(4) {
+        let word = core::ptr::read_volatile((load + offset) as *const u32);
+        core::ptr::write_volatile((start + offset) as *mut u32, word);
+    }
+
+    // The copied code must not be fetched before it has been written
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+/// Invalidates the data cache lines holding `len` bytes starting at `address`
+///
+/// The flash is cacheable on the AXIM interface, and an erase doesn't update the cache.
+fn invalidate_dcache(address: usize, len: usize) {
+    const DCIMVAC: *mut u32 = 0xE000_EF5C as *mut u32;
+    const LINE_SIZE: usize = 32;
+
+    cortex_m::asm::dsb();
+    let mut line = address & !(LINE_SIZE - 1);
+    while line < address + len {
+        // NOTE(unsafe) write-only cache maintenance register, this is a no-op with the cache off
+        unsafe { core::ptr::write_volatile(DCIMVAC, line as u32) };
+        line += LINE_SIZE;
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+/// Returns `true` if `len` bytes starting at `address` lie within the flash memory
+fn is_programmable(address: usize, len: usize) -> bool {
+    address >= FLASH_BASE && address + len <= FLASH_BASE + flash_size()
+}
+
+/// Start address of the one-time programmable area
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+pub const OTP_BASE: usize = 0x1FF0_7800;
+/// Start address of the one-time programmable area
+#[cfg(any(
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
+pub const OTP_BASE: usize = 0x1FF0_F000;
+/// Number of OTP blocks
+pub const OTP_BLOCKS: usize = 16;
+/// Size of an OTP block in bytes
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+pub const OTP_BLOCK_SIZE: usize = 32;
+/// Size of an OTP block in bytes
+#[cfg(any(
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
+pub const OTP_BLOCK_SIZE: usize = 64;
+
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+const OTP_LOCK_BASE: usize = 0x1FF0_7A00;
+#[cfg(any(
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
+const OTP_LOCK_BASE: usize = 0x1FF0_F400;
+
+/// Returns the contents of an OTP block
+pub fn read_otp(block: usize) -> Option<&'static [u8]> {
+    if block >= OTP_BLOCKS {
+        return None;
+    }
+
+    // NOTE(unsafe) the OTP area is memory mapped and only changes through `UnlockedFlash`
+    Some(unsafe {
+        core::slice::from_raw_parts(
+            (OTP_BASE + block * OTP_BLOCK_SIZE) as *const u8,
+            OTP_BLOCK_SIZE,
+        )
+    })
+}
+
+/// Returns `true` if an OTP block is locked
+pub fn is_otp_block_locked(block: usize) -> bool {
+    // NOTE(unsafe) atomic read with no side effects
+    block < OTP_BLOCKS
+        && unsafe { core::ptr::read_volatile((OTP_LOCK_BASE + block) as *const u8) } != 0xFF
+}
+
+/// Read protection level
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RdpLevel {
+    /// No read protection
+    Level0,
+    /// Debug and boot from RAM / system memory can't access the flash
+    Level1,
+    /// Chip protection. This can NOT be undone
+    Level2,
+}
+
+impl RdpLevel {
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            0xAA => RdpLevel::Level0,
+            0xCC => RdpLevel::Level2,
+            _ => RdpLevel::Level1,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        match self {
+            RdpLevel::Level0 => 0xAA,
+            RdpLevel::Level1 => 0x55,
+            RdpLevel::Level2 => 0xCC,
+        }
+    }
+}
+
+/// Brownout reset threshold level
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BorLevel {
+    /// Reset threshold around 2.70 - 3.60 V
+    Level3,
+    /// Reset threshold around 2.40 - 2.70 V
+    Level2,
+    /// Reset threshold around 2.10 - 2.40 V
+    Level1,
+    /// POR / PDR reset threshold only
+    Off,
+}
+
+impl BorLevel {
+    fn from_bits(bits: u32) -> Self {
+        match bits & 0b11 {
+            0b00 => BorLevel::Level3,
+            0b01 => BorLevel::Level2,
+            0b10 => BorLevel::Level1,
+            _ => BorLevel::Off,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        match self {
+            BorLevel::Level3 => 0b00,
+            BorLevel::Level2 => 0b01,
+            BorLevel::Level1 => 0b10,
+            BorLevel::Off => 0b11,
+        }
+    }
+}
+
+/// User option bytes
+///
+/// Flags are expressed in positive logic, i.e. `write_protection` has a bit set for every
+/// protected sector, even though the hardware uses the inverted `nWRP` bits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptionBytes {
+    /// Read protection level
+    pub rdp: RdpLevel,
+    /// Brownout reset level
+    pub bor: BorLevel,
+    /// Write protected sectors, one bit per sector
+    pub write_protection: u16,
+    /// Independent watchdog is started by hardware after reset
+    pub iwdg_hw: bool,
+    /// Window watchdog is started by hardware after reset
+    pub wwdg_hw: bool,
+    /// A reset is generated when entering Stop mode
+    pub reset_on_stop: bool,
+    /// A reset is generated when entering Standby mode
+    pub reset_on_standby: bool,
+    /// The independent watchdog counter is frozen in Stop mode
+    pub iwdg_freeze_stop: bool,
+    /// The independent watchdog counter is frozen in Standby mode
+    pub iwdg_freeze_standby: bool,
+    /// Boot address when BOOT0 = 0, bits \[29:14\] of the address
+    pub boot_add0: u16,
+    /// Boot address when BOOT0 = 1, bits \[29:14\] of the address
+    pub boot_add1: u16,
+    /// The flash is organized as two banks (`nDBANK` cleared), always `false` on the STM32F745
+    #[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+    pub dual_bank: bool,
+    /// Booting from either bank is enabled (`nDBOOT` cleared), always `false` on the STM32F745
+    #[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+    pub dual_boot: bool,
+}
+
+impl OptionBytes {
+    /// Reads the currently loaded option bytes
+    pub fn read(optcr: &mut OPTCR, optcr1: &mut OPTCR1) -> Self {
+        let bits = optcr.optcr().read().bits();
+        let boot = optcr1.optcr1().read().bits();
+        let (nwrp_mask, _) = optcr_masks();
+
+        OptionBytes {
+            rdp: RdpLevel::from_bits((bits >> 8) as u8),
+            bor: BorLevel::from_bits(bits >> 2),
+            write_protection: (!(bits >> 16) & nwrp_mask) as u16,
+            iwdg_hw: bits & (1 << 5) == 0,
+            wwdg_hw: bits & (1 << 4) == 0,
+            reset_on_stop: bits & (1 << 6) == 0,
+            reset_on_standby: bits & (1 << 7) == 0,
+            iwdg_freeze_stop: bits & (1 << 31) == 0,
+            iwdg_freeze_standby: bits & (1 << 30) == 0,
+            boot_add0: boot as u16,
+            boot_add1: (boot >> 16) as u16,
+            #[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+            dual_bank: is_f76x() && bits & (1 << 29) == 0,
+            #[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+            dual_boot: is_f76x() && bits & (1 << 28) == 0,
+        }
+    }
+
+    /// Programs the option bytes
+    ///
+    /// The new values are loaded on the next system reset. Setting `rdp` to `RdpLevel::Level2`
+    /// permanently disables debugging and option byte changes, and going from level 1 back to
+    /// level 0 mass erases the flash.
+    pub fn program(
+        &self,
+        optcr: &mut OPTCR,
+        optcr1: &mut OPTCR1,
+        optkeyr: &mut OPTKEYR,
+        sr: &mut SR,
+    ) -> Result<(), Error> {
+        sr.wait()?;
+
+        if optcr.optcr().read().bits() & OPTCR_OPTLOCK != 0 {
+            optkeyr.optkeyr().write(|w| unsafe { w.bits(OPTKEY1) });
+            optkeyr.optkeyr().write(|w| unsafe { w.bits(OPTKEY2) });
+
+            if optcr.optcr().read().bits() & OPTCR_OPTLOCK != 0 {
+                return Err(Error::Locked);
+            }
+        }
+
+        optcr1.optcr1().write(|w| unsafe {
+            w.bits(u32::from(self.boot_add0) | (u32::from(self.boot_add1) << 16))
+        });
+
+        // Reserved bits keep their reset value
+        let (nwrp_mask, reserved) = optcr_masks();
+        let mut bits = optcr.optcr().read().bits() & reserved;
+        bits |= (self.bor.bits() << 2) | (u32::from(self.rdp.bits()) << 8);
+        bits |= (!u32::from(self.write_protection) & nwrp_mask) << 16;
+        if !self.wwdg_hw {
+            bits |= 1 << 4;
+        }
+        if !self.iwdg_hw {
+            bits |= 1 << 5;
+        }
+        if !self.reset_on_stop {
+            bits |= 1 << 6;
+        }
+        if !self.reset_on_standby {
+            bits |= 1 << 7;
+        }
+        // These bits are reserved on the STM32F745
+        #[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+        {
+            if is_f76x() && !self.dual_boot {
+                bits |= 1 << 28;
+            }
+            if is_f76x() && !self.dual_bank {
+                bits |= 1 << 29;
+            }
+        }
+        if !self.iwdg_freeze_standby {
+            bits |= 1 << 30;
+        }
+        if !self.iwdg_freeze_stop {
+            bits |= 1 << 31;
+        }
+
+        optcr.optcr().write(|w| unsafe { w.bits(bits) });
+        optcr.optcr().modify(|r, w| unsafe { w.bits(r.bits() | OPTCR_OPTSTRT) });
+        let result = sr.wait();
+
+        // Lock the option bytes again
+        optcr.optcr().modify(|r, w| unsafe { w.bits(r.bits() | OPTCR_OPTLOCK) });
+
+        result
+    }
+}
+
+/// Flash bank of a dual bank device
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bank {
+    /// Bank 1, sectors 0 to 11 (0 to 7 on 1 MB devices)
+    Bank1,
+    /// Bank 2, sectors 12 to 23 (12 to 19 on 1 MB devices)
+    Bank2,
+}
+
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+impl Bank {
+    /// Returns the bank the running code is executed from
+    ///
+    /// Returns `None` if the code is executed from RAM or the flash is a single bank.
+    pub fn active() -> Option<Self> {
+        if !is_dual_bank() {
+            return None;
+        }
+
+        let address = Bank::active as fn() -> Option<Self> as usize;
+        let offset = flash_offset(address)?;
+
+        if offset < flash_size() / 2 {
+            Some(Bank::Bank1)
+        } else {
+            Some(Bank::Bank2)
+        }
+    }
+
+    /// Returns the other bank
+    pub fn other(self) -> Self {
+        match self {
+            Bank::Bank1 => Bank::Bank2,
+            Bank::Bank2 => Bank::Bank1,
+        }
+    }
+
+    /// Start address of the bank
+    pub fn address(self) -> usize {
+        match self {
+            Bank::Bank1 => FLASH_BASE,
+            Bank::Bank2 => FLASH_BASE + flash_size() / 2,
+        }
+    }
+
+    /// Size of the bank in bytes
+    pub fn size(self) -> usize {
+        flash_size() / 2
+    }
+
+    /// Returns `true` if the bank contents starting `offset` bytes into the bank match `image`
+    pub fn verify(self, offset: usize, image: &[u8]) -> bool {
+        if offset + image.len() > self.size() {
+            return false;
+        }
+
+        // NOTE(unsafe) the flash is memory mapped and the range was checked above
+        let contents = unsafe {
+            core::slice::from_raw_parts((self.address() + offset) as *const u8, image.len())
+        };
+        contents == image
+    }
+
+    fn sectors(self) -> impl Iterator<Item = Sector> {
+        let first = match self {
+            Bank::Bank1 => 0,
+            Bank::Bank2 => 12,
+        };
+        (first..first + 12).filter_map(Sector::new)
+    }
+}
+
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+impl<'a> UnlockedFlash<'a> {
+    /// Erases the bank that is not executing the running code
+    pub fn erase_inactive_bank(&mut self) -> Result<(), Error> {
+        if !is_dual_bank() {
+            return Err(Error::SingleBank);
+        }
+
+        let bank = Bank::active().ok_or(Error::NoActiveBank)?.other();
+        for sector in bank.sectors() {
+            self.erase_sector(&sector)?;
+        }
+
+        Ok(())
+    }
+
+    /// Programs `data` into the bank that is not executing the running code, starting `offset`
+    /// bytes into the bank
+    pub fn program_inactive_bank(&mut self, offset: usize, data: &[u8]) -> Result<(), Error> {
+        if !is_dual_bank() {
+            return Err(Error::SingleBank);
+        }
+
+        let bank = Bank::active().ok_or(Error::NoActiveBank)?.other();
+        if offset + data.len() > bank.size() {
+            return Err(Error::InvalidAddress);
+        }
+
+        self.program(bank.address() + offset, data)
+    }
+}
+
+/// Makes the device boot from `bank` after the next reset
+///
+/// Images are not remapped, so an image booted from bank 2 must be linked to run from the
+/// bank 2 address. Switching back to the previous bank rolls the update back.
+///
+/// `BOOT_ADD0` always selects `bank`. `BOOT_ADD1`, used when BOOT0 is high, is only changed if it
+/// points into the flash, so that e.g. the system bootloader stays reachable.
+#[cfg(any(feature = "stm32f7x5", feature = "stm32f7x7", feature = "stm32f7x9"))]
+pub fn set_boot_bank(
+    bank: Bank,
+    optcr: &mut OPTCR,
+    optcr1: &mut OPTCR1,
+    optkeyr: &mut OPTKEYR,
+    sr: &mut SR,
+) -> Result<(), Error> {
+    if !is_dual_bank() {
+        return Err(Error::SingleBank);
+    }
+
+    let mut option_bytes = OptionBytes::read(optcr, optcr1);
+
+    // BOOT_ADDx hold bits [29:14] of the boot address
+    let boot_add = (bank.address() >> 14) as u16;
+    option_bytes.boot_add0 = boot_add;
+    if flash_offset(usize::from(option_bytes.boot_add1) << 14).is_some() {
+        option_bytes.boot_add1 = boot_add;
+    }
+    option_bytes.program(optcr, optcr1, optkeyr, sr)
+}
@@ -3,9 +3,13 @@
 // Based on (ripped)
 // https://github.com/japaric/stm32f30x-hal/blob/master/src/gpio.rs
 
+use core::convert::Infallible;
 use core::marker::PhantomData;
 
+use crate::hal::digital::v2::{InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin};
 use crate::rcc::AHB1;
+use crate::stm32::EXTI;
+use crate::syscfg::SysCfg;
 
 /// Extension trait to split a GPIO peripheral in independent pins and registers
 pub trait GpioExt {
@@ -27,6 +31,9 @@ pub struct PullDown;
 /// Pulled up input (type state)
 pub struct PullUp;
 
+/// Analog mode (type state)
+pub struct Analog;
+
 /// Output mode (type state)
 pub struct Output<MODE> {
     _mode: PhantomData<MODE>,
@@ -36,12 +43,310 @@ pub struct PushPull;
 /// Open drain output (type state)
 pub struct OpenDrain;
 
+/// Dynamic mode (type state)
+///
+/// The direction and pull of the pin can be changed at runtime without consuming it
+pub struct Dynamic;
+
+/// Runtime configuration of a dynamic pin
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DynamicMode {
+    /// Floating input
+    FloatingInput,
+    /// Pulled up input
+    PullUpInput,
+    /// Pulled down input
+    PullDownInput,
+    /// Push-pull output
+    PushPullOutput,
+    /// Open drain output
+    OpenDrainOutput,
+    /// Alternate function or analog mode
+    Other,
+}
+
+/// Dynamic pin errors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PinModeError {
+    /// The pin is not configured in a mode supporting the operation
+    IncorrectMode,
+}
+
 /// Alternate mode (type state)
 pub struct Alternate<AF, MODE> {
     _af: PhantomData<AF>,
     _mode: PhantomData<MODE>,
 }
 
+/// GPIO output speed
+///
+/// The very high speed requires the I/O compensation cell, so it's only available through
+/// `set_very_high_speed`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Speed {
+    /// Low speed
+    Low,
+    /// Medium speed
+    Medium,
+    /// High speed
+    High,
+}
+
+/// External interrupt trigger edge
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edge {
+    /// Rising edge
+    Rising,
+    /// Falling edge
+    Falling,
+    /// Rising and falling edges
+    RisingFalling,
+}
+
+/// External interrupt configuration of an input pin
+pub trait ExtiPin {
+    /// Routes the pin to its EXTI line
+    fn make_interrupt_source(&mut self, syscfg: &mut SysCfg);
+    /// Selects the edges generating an interrupt
+    fn trigger_on_edge(&mut self, exti: &mut EXTI, edge: Edge);
+    /// Unmasks the interrupt of the EXTI line
+    fn enable_interrupt(&mut self, exti: &mut EXTI);
+    /// Masks the interrupt of the EXTI line
+    fn disable_interrupt(&mut self, exti: &mut EXTI);
+    /// Clears the pending bit of the EXTI line
+    fn clear_interrupt_pending_bit(&mut self);
+    /// Returns `true` if the EXTI line has a pending interrupt
+    fn check_interrupt(&self) -> bool;
+}
+
+fn exti_make_source(syscfg: &mut SysCfg, port: u8, i: u8) {
+    let offset = 4 * (i % 4);
+    let mask = !(0b1111 << offset);
+    let value = u32::from(port) << offset;
+    let syscfg = syscfg.regs();
+    match i / 4 {
+        0 => syscfg
+            .exticr1
+            .modify(|r, w| unsafe { w.bits((r.bits() & mask) | value) }),
+        1 => syscfg
+            .exticr2
+            .modify(|r, w| unsafe { w.bits((r.bits() & mask) | value) }),
+        2 => syscfg
+            .exticr3
+            .modify(|r, w| unsafe { w.bits((r.bits() & mask) | value) }),
+        _ => syscfg
+            .exticr4
+            .modify(|r, w| unsafe { w.bits((r.bits() & mask) | value) }),
+    }
+}
+
+fn exti_trigger_on_edge(exti: &mut EXTI, i: u8, edge: Edge) {
+    let (rising, falling) = match edge {
+        Edge::Rising => (true, false),
+        Edge::Falling => (false, true),
+        Edge::RisingFalling => (true, true),
+    };
+    exti.rtsr.modify(|r, w| unsafe {
+        w.bits(if rising {
+            r.bits() | (1 << i)
+        } else {
+            r.bits() & !(1 << i)
+        })
+    });
+    exti.ftsr.modify(|r, w| unsafe {
+        w.bits(if falling {
+            r.bits() | (1 << i)
+        } else {
+            r.bits() & !(1 << i)
+        })
+    });
+}
+
+fn exti_enable_interrupt(exti: &mut EXTI, i: u8) {
+    exti.imr
+        .modify(|r, w| unsafe { w.bits(r.bits() | (1 << i)) });
+}
+
+fn exti_disable_interrupt(exti: &mut EXTI, i: u8) {
+    exti.imr
+        .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << i)) });
+}
+
+fn exti_clear_interrupt_pending_bit(i: u8) {
+    // NOTE(unsafe) atomic write to a write-1-to-clear register
+    unsafe { (*EXTI::ptr()).pr.write(|w| w.bits(1 << i)) }
+}
+
+fn exti_check_interrupt(i: u8) -> bool {
+    // NOTE(unsafe) atomic read with no side effects
+    unsafe { (*EXTI::ptr()).pr.read().bits() & (1 << i) != 0 }
+}
+
+/// Mode a pin can be configured in generically
+pub trait PinMode {
+    /// MODER field
+    const MODER: u32;
+    /// OTYPER field, `None` if the mode doesn't depend on it
+    const OTYPER: Option<u32>;
+    /// PUPDR field, `None` if the mode doesn't depend on it
+    const PUPDR: Option<u32>;
+    /// AFR field, `None` if the mode doesn't depend on it
+    const AFR: Option<u32>;
+}
+
+impl PinMode for Input<Floating> {
+    const MODER: u32 = 0b00;
+    const OTYPER: Option<u32> = None;
+    const PUPDR: Option<u32> = Some(0b00);
+    const AFR: Option<u32> = None;
+}
+
+impl PinMode for Input<PullDown> {
+    const MODER: u32 = 0b00;
+    const OTYPER: Option<u32> = None;
+    const PUPDR: Option<u32> = Some(0b10);
+    const AFR: Option<u32> = None;
+}
+
+impl PinMode for Input<PullUp> {
+    const MODER: u32 = 0b00;
+    const OTYPER: Option<u32> = None;
+    const PUPDR: Option<u32> = Some(0b01);
+    const AFR: Option<u32> = None;
+}
+
+impl PinMode for Output<PushPull> {
+    const MODER: u32 = 0b01;
+    const OTYPER: Option<u32> = Some(0b0);
+    const PUPDR: Option<u32> = None;
+    const AFR: Option<u32> = None;
+}
+
+impl PinMode for Output<OpenDrain> {
+    const MODER: u32 = 0b01;
+    const OTYPER: Option<u32> = Some(0b1);
+    const PUPDR: Option<u32> = None;
+    const AFR: Option<u32> = None;
+}
+
+impl<AF: AlternateFunction> PinMode for Alternate<AF, PushPull> {
+    const MODER: u32 = 0b10;
+    const OTYPER: Option<u32> = Some(0b0);
+    const PUPDR: Option<u32> = None;
+    const AFR: Option<u32> = Some(AF::NUMBER);
+}
+
+impl<AF: AlternateFunction> PinMode for Alternate<AF, OpenDrain> {
+    const MODER: u32 = 0b10;
+    const OTYPER: Option<u32> = Some(0b1);
+    const PUPDR: Option<u32> = None;
+    const AFR: Option<u32> = Some(AF::NUMBER);
+}
+
+impl PinMode for Analog {
+    const MODER: u32 = 0b11;
+    const OTYPER: Option<u32> = None;
+    const PUPDR: Option<u32> = Some(0b00);
+    const AFR: Option<u32> = None;
+}
+
+/// Configuration fields of a pin, `None` leaves a field unchanged
+#[derive(Clone, Copy)]
+struct Config {
+    moder: Option<u32>,
+    otyper: Option<u32>,
+    pupdr: Option<u32>,
+    afr: Option<u32>,
+}
+
+impl Config {
+    const UNCHANGED: Config = Config {
+        moder: None,
+        otyper: None,
+        pupdr: None,
+        afr: None,
+    };
+
+    fn of<M: PinMode>() -> Config {
+        Config {
+            moder: Some(M::MODER),
+            otyper: M::OTYPER,
+            pupdr: M::PUPDR,
+            afr: M::AFR,
+        }
+    }
+
+    /// Reads the current configuration of pin `i` of `port`
+    fn read(port: u8, i: u8) -> Config {
+        // NOTE(unsafe) atomic reads with no side effects
+        let gpio = unsafe { &*port_ptr(port) };
+        let offset = 2 * i;
+        let afr = if i < 8 {
+            gpio.afrl.read().bits()
+        } else {
+            gpio.afrh.read().bits()
+        };
+
+        Config {
+            moder: Some((gpio.moder.read().bits() >> offset) & 0b11),
+            otyper: Some((gpio.otyper.read().bits() >> i) & 0b1),
+            pupdr: Some((gpio.pupdr.read().bits() >> offset) & 0b11),
+            afr: Some((afr >> (4 * (i % 8))) & 0b1111),
+        }
+    }
+
+    /// Configures pin `i` of `port`
+    ///
+    /// The mode is written last so the pin never runs with a partial configuration.
+    fn write(self, port: u8, i: u8) {
+        // NOTE(unsafe) the read-modify-writes run in a critical section and only change the
+        // fields of the pin, which is owned by the caller
+        let gpio = unsafe { &*port_ptr(port) };
+        let offset = 2 * i;
+
+        critical_section(|| unsafe {
+            if let Some(otyper) = self.otyper {
+                gpio.otyper
+                    .modify(|r, w| w.bits((r.bits() & !(0b1 << i)) | (otyper << i)));
+            }
+            if let Some(pupdr) = self.pupdr {
+                gpio.pupdr
+                    .modify(|r, w| w.bits((r.bits() & !(0b11 << offset)) | (pupdr << offset)));
+            }
+            if let Some(afr) = self.afr {
+                let offset = 4 * (i % 8);
+                if i < 8 {
+                    gpio.afrl
+                        .modify(|r, w| w.bits((r.bits() & !(0b1111 << offset)) | (afr << offset)));
+                } else {
+                    gpio.afrh
+                        .modify(|r, w| w.bits((r.bits() & !(0b1111 << offset)) | (afr << offset)));
+                }
+            }
+            if let Some(moder) = self.moder {
+                gpio.moder
+                    .modify(|r, w| w.bits((r.bits() & !(0b11 << offset)) | (moder << offset)));
+            }
+        });
+    }
+}
+
+/// Alternate function number of an `AFn` type state
+pub trait AlternateFunction {
+    /// Value written to the AFR register
+    const NUMBER: u32;
+}
+
+macro_rules! alternate_function_numbers {
+    ($($AFn:ident: $n:expr,)+) => {
+        $(
+            impl AlternateFunction for $AFn {
+                const NUMBER: u32 = $n;
+            }
+        )+
+    };
+}
+
 /// Alternate function 0 (type state)
 pub struct AF0;
 /// Alternate function 1 (type state)
@@ -75,23 +380,358 @@ pub struct AF14;
 /// Alternate function 15 (type state)
 pub struct AF15;
 
+alternate_function_numbers!(
+    AF0: 0, AF1: 1, AF2: 2, AF3: 3, AF4: 4, AF5: 5, AF6: 6, AF7: 7,
+    AF8: 8, AF9: 9, AF10: 10, AF11: 11, AF12: 12, AF13: 13, AF14: 14, AF15: 15,
+);
+
+/// Fully erased pin
+pub struct Pin<MODE> {
+    port: u8,
+    i: u8,
+    _mode: PhantomData<MODE>,
+}
+
+impl<MODE> Pin<MODE> {
+    /// Configures the pin to operate in mode `M`
+    pub fn into_mode<M: PinMode>(self) -> Pin<M> {
+        Config::of::<M>().write(self.port, self.i);
+
+        Pin {
+            port: self.port,
+            i: self.i,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Temporarily configures the pin to operate in mode `M`
+    ///
+    /// The previous configuration is restored when `f` returns.
+    pub fn with_mode<M: PinMode, R>(&mut self, f: impl FnOnce(&mut Pin<M>) -> R) -> R {
+        let previous = Config::read(self.port, self.i);
+        Config::of::<M>().write(self.port, self.i);
+
+        let r = f(&mut Pin {
+            port: self.port,
+            i: self.i,
+            _mode: PhantomData,
+        });
+
+        previous.write(self.port, self.i);
+        r
+    }
+
+    fn block(&self) -> &regs::gpioa::RegisterBlock {
+        // NOTE(unsafe) the pin only uses atomic operations on its port
+        unsafe { &*port_ptr(self.port) }
+    }
+}
+
+impl<MODE> OutputPin for Pin<Output<MODE>> {
+    type Error = Infallible;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        // NOTE(unsafe) atomic write to a stateless register
+        self.block().bsrr.write(|w| unsafe { w.bits(1 << self.i) });
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        // NOTE(unsafe) atomic write to a stateless register
+        self.block()
+            .bsrr
+            .write(|w| unsafe { w.bits(1 << (16 + self.i)) });
+        Ok(())
+    }
+}
+
+impl<MODE> StatefulOutputPin for Pin<Output<MODE>> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.is_set_low().map(|b| !b)
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.block().odr.read().bits() & (1 << self.i) == 0)
+    }
+}
+
+impl<MODE> ToggleableOutputPin for Pin<Output<MODE>> {
+    type Error = Infallible;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        let i = if self.is_set_low()? {
+            self.i
+        } else {
+            16 + self.i
+        };
+        // NOTE(unsafe) atomic write to a stateless register
+        self.block().bsrr.write(|w| unsafe { w.bits(1 << i) });
+        Ok(())
+    }
+}
+
+impl<MODE> InputPin for Pin<Input<MODE>> {
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.is_low().map(|b| !b)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.block().idr.read().bits() & (1 << self.i) == 0)
+    }
+}
+
+/// Reads the level of the line, which other devices may pull low
+impl InputPin for Pin<Output<OpenDrain>> {
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.is_low().map(|b| !b)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        Ok(self.block().idr.read().bits() & (1 << self.i) == 0)
+    }
+}
+
+impl<MODE> ExtiPin for Pin<Input<MODE>> {
+    fn make_interrupt_source(&mut self, syscfg: &mut SysCfg) {
+        exti_make_source(syscfg, self.port, self.i)
+    }
+
+    fn trigger_on_edge(&mut self, exti: &mut EXTI, edge: Edge) {
+        exti_trigger_on_edge(exti, self.i, edge)
+    }
+
+    fn enable_interrupt(&mut self, exti: &mut EXTI) {
+        exti_enable_interrupt(exti, self.i)
+    }
+
+    fn disable_interrupt(&mut self, exti: &mut EXTI) {
+        exti_disable_interrupt(exti, self.i)
+    }
+
+    fn clear_interrupt_pending_bit(&mut self) {
+        exti_clear_interrupt_pending_bit(self.i)
+    }
+
+    fn check_interrupt(&self) -> bool {
+        exti_check_interrupt(self.i)
+    }
+}
+
+/// Pin whose configuration is locked until the next reset (type state)
+///
+/// The pin can still be driven and sampled but can no longer be reconfigured.
+pub struct Locked<P> {
+    pin: P,
+}
+
+impl<P> Locked<P> {
+    /// Returns a reference to the locked pin
+    pub fn pin(&self) -> &P {
+        &self.pin
+    }
+}
+
+/// Private supertrait of the per port `Lockable` traits
+///
+/// `Locked` pins can only be created with a `LockKey`, which only `LCKR::lock` hands out after
+/// running the lock key sequence.
+mod sealed {
+    pub struct LockKey(pub(super) ());
+
+    pub trait Lock {
+        type Locked;
+
+        fn lock(self, key: &LockKey) -> Self::Locked;
+    }
+
+    impl<A: Lock, B: Lock> Lock for (A, B) {
+        type Locked = (A::Locked, B::Locked);
+
+        fn lock(self, key: &LockKey) -> Self::Locked {
+            (self.0.lock(key), self.1.lock(key))
+        }
+    }
+
+    impl<A: Lock, B: Lock, C: Lock> Lock for (A, B, C) {
+        type Locked = (A::Locked, B::Locked, C::Locked);
+
+        fn lock(self, key: &LockKey) -> Self::Locked {
+            (self.0.lock(key), self.1.lock(key), self.2.lock(key))
+        }
+    }
+
+    impl<A: Lock, B: Lock, C: Lock, D: Lock> Lock for (A, B, C, D) {
+        type Locked = (A::Locked, B::Locked, C::Locked, D::Locked);
+
+        fn lock(self, key: &LockKey) -> Self::Locked {
+            (
+                self.0.lock(key),
+                self.1.lock(key),
+                self.2.lock(key),
+                self.3.lock(key),
+            )
+        }
+    }
+}
+
+impl<P: OutputPin> OutputPin for Locked<P> {
+    type Error = P::Error;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_high()
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.pin.set_low()
+    }
+}
+
+impl<P: StatefulOutputPin> StatefulOutputPin for Locked<P> {
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        self.pin.is_set_high()
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        self.pin.is_set_low()
+    }
+}
+
+impl<P: ToggleableOutputPin> ToggleableOutputPin for Locked<P> {
+    type Error = P::Error;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        self.pin.toggle()
+    }
+}
+
+impl<P: InputPin> InputPin for Locked<P> {
+    type Error = P::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.pin.is_high()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.pin.is_low()
+    }
+}
+
+impl<P: ExtiPin> ExtiPin for Locked<P> {
+    fn make_interrupt_source(&mut self, syscfg: &mut SysCfg) {
+        self.pin.make_interrupt_source(syscfg)
+    }
+
+    fn trigger_on_edge(&mut self, exti: &mut EXTI, edge: Edge) {
+        self.pin.trigger_on_edge(exti, edge)
+    }
+
+    fn enable_interrupt(&mut self, exti: &mut EXTI) {
+        self.pin.enable_interrupt(exti)
+    }
+
+    fn disable_interrupt(&mut self, exti: &mut EXTI) {
+        self.pin.disable_interrupt(exti)
+    }
+
+    fn clear_interrupt_pending_bit(&mut self) {
+        self.pin.clear_interrupt_pending_bit()
+    }
+
+    fn check_interrupt(&self) -> bool {
+        self.pin.check_interrupt()
+    }
+}
+
+/// Returns the register block of a port
+///
+/// The ports are laid out every 0x400 bytes and share the same register layout.
+#[cfg(not(test))]
+fn port_ptr(port: u8) -> *const regs::gpioa::RegisterBlock {
+    (crate::stm32::GPIOA::ptr() as usize + 0x400 * usize::from(port)) as *const _
+}
+
+#[cfg(test)]
+fn port_ptr(port: u8) -> *const regs::gpioa::RegisterBlock {
+    tests::port_ptr(port)
+}
+
+/// Runs `f` with interrupts disabled, making the configuration read-modify-writes atomic
+#[cfg(not(test))]
+fn critical_section<R>(f: impl FnOnce() -> R) -> R {
+    cortex_m::interrupt::free(|_| f())
+}
+
+#[cfg(test)]
+fn critical_section<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+/// Register block definitions
+///
+/// The SVD derives the ports from C upwards from GPIOD, or from GPIOH on F72x/F73x devices.
+mod regs {
+    #[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+    pub use crate::stm32::gpiod;
+    #[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+    pub use crate::stm32::gpioh as gpiod;
+    pub use crate::stm32::{gpioa, gpiob};
+}
+
+macro_rules! alternate_functions {
+    ($PXi:ident, $AFR:ident, [
+        $($AFn:ident: ($into_afn:ident, $into_afn_open_drain:ident),)+
+    ]) => {
+        $(
+            /// Configures the pin to operate as a push-pull alternate function pin
+            pub fn $into_afn(
+                self,
+                _moder: &mut MODER,
+                _otyper: &mut OTYPER,
+                _afr: &mut $AFR,
+            ) -> $PXi<Alternate<$AFn, PushPull>> {
+                self.into_mode()
+            }
+
+            /// Configures the pin to operate as an open-drain alternate function pin
+            pub fn $into_afn_open_drain(
+                self,
+                _moder: &mut MODER,
+                _otyper: &mut OTYPER,
+                _afr: &mut $AFR,
+            ) -> $PXi<Alternate<$AFn, OpenDrain>> {
+                self.into_mode()
+            }
+        )+
+    };
+}
+
 macro_rules! gpio {
-    ($GPIOX: ident, $gpiox: ident, $gpioy: ident, $iopxenr: ident, $iopxrst: ident, $PXx: ident, [
+    ($GPIOX: ident, $gpiox: ident, $gpioy: ident, $iopxenr: ident, $iopxrst: ident, $PXx: ident, $port_id: expr, [
         $($PXi: ident: ($pxi: ident, $i: expr, $MODE: ty, $AFR: ident),)+
     ]) => {
         /// GPIO
         pub mod $gpiox {
+            use core::convert::Infallible;
             use core::marker::PhantomData;
 
-            use crate::hal::digital::{InputPin, OutputPin};
-            use crate::stm32::{$GPIOX, $gpioy};
+            use crate::hal::digital::v2::{InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin};
+            use super::regs::$gpioy;
+            use crate::stm32::$GPIOX;
 
             use super::{
-                //Alternate,
-                Floating, GpioExt, Input, OpenDrain, Output, PullDown, PullUp, PushPull,
-                //AF4, AF5, AF6, AF7, AF8, AF9,
+                Alternate, Analog, Dynamic, DynamicMode, Edge, ExtiPin, Floating,
+                GpioExt, Input, Locked, OpenDrain, Output, Pin, PinMode, PinModeError, PullDown,
+                PullUp, PushPull, Speed, AF0, AF1, AF2, AF3, AF4, AF5, AF6, AF7, AF8, AF9, AF10, AF11, AF12,
+                AF13, AF14, AF15,
             };
+            use super::sealed::{Lock, LockKey};
             use crate::rcc::AHB1;
+            use crate::stm32::EXTI;
+            use crate::syscfg::{CompensationCell, SysCfg};
 
             /// GPIO ports
             pub struct Parts {
@@ -99,8 +739,12 @@ macro_rules! gpio {
                 pub afrh: AFRH,
                 /// Opaque AFRL register
                 pub afrl: AFRL,
+                /// Opaque LCKR register
+                pub lckr: LCKR,
                 /// Opaque MODER register
                 pub moder: MODER,
+                /// Opaque OSPEEDR register
+                pub ospeedr: OSPEEDR,
                 /// Opaque OTYPER register
                 pub otyper: OTYPER,
                 /// Opaque PUPDR register
@@ -119,10 +763,18 @@ macro_rules! gpio {
                     ahb.rstr().modify(|_, w| w.$iopxrst().set_bit());
                     ahb.rstr().modify(|_, w| w.$iopxrst().clear_bit());
 
+                    Parts::new()
+                }
+            }
+
+            impl Parts {
+                pub(super) fn new() -> Self {
                     Parts {
                         afrh: AFRH { _0: () },
                         afrl: AFRL { _0: () },
+                        lckr: LCKR { _0: () },
                         moder: MODER { _0: () },
+                        ospeedr: OSPEEDR { _0: () },
                         otyper: OTYPER { _0: () },
                         pupdr: PUPDR { _0: () },
                         $(
@@ -132,28 +784,89 @@ macro_rules! gpio {
                 }
             }
 
+            /// Returns the register block of the port
+            fn ptr() -> *const $gpioy::RegisterBlock {
+                super::port_ptr($port_id) as *const _
+            }
+
             /// Opaque AFRH register
             pub struct AFRH {
                 _0: ()
             }
 
-            impl AFRH {
-                // TODO: remove `allow`
-                #[allow(dead_code)]
-                pub(crate) fn afr(&mut self) -> &$gpioy::AFRH {
-                    unsafe { &(*$GPIOX::ptr()).afrh }
-                }
-            }
-
             /// Opaque AFRL register
             pub struct AFRL {
                 _0: ()
             }
 
-            impl AFRL {
-                #[allow(dead_code)]
-                pub(crate) fn afr(&mut self) -> &$gpioy::AFRL {
-                    unsafe { &(*$GPIOX::ptr()).afrl }
+            /// Opaque LCKR register
+            pub struct LCKR {
+                _0: ()
+            }
+
+            impl LCKR {
+                /// Locks the configuration of `pins` until the next reset
+                ///
+                /// `pins` is a pin or a tuple of pins of this port. The lock register can only be
+                /// written once, so all the pins to protect must be locked together. The pins are
+                /// returned unlocked if the lock didn't latch.
+                pub fn lock<P: Lockable>(self, pins: P) -> Result<P::Locked, P> {
+                    let mask = u32::from(pins.mask());
+                    let lckk = 1 << 16;
+
+                    // NOTE(unsafe) this proxy owns the LCKR register
+                    let lckr = unsafe { &(*ptr()).lckr };
+
+                    // Lock key write sequence
+                    lckr.write(|w| unsafe { w.bits(lckk | mask) });
+                    lckr.write(|w| unsafe { w.bits(mask) });
+                    lckr.write(|w| unsafe { w.bits(lckk | mask) });
+                    let _ = lckr.read();
+
+                    let bits = lckr.read().bits();
+                    if bits & lckk == 0 || bits & mask != mask {
+                        return Err(pins);
+                    }
+
+                    Ok(pins.lock(&LockKey(())))
+                }
+            }
+
+            /// Pins of this port whose configuration can be locked
+            pub trait Lockable: Lock {
+                /// Returns the pins as a bit mask
+                fn mask(&self) -> u16;
+            }
+
+            impl<MODE> Lock for $PXx<MODE> {
+                type Locked = Locked<Self>;
+
+                fn lock(self, _key: &LockKey) -> Self::Locked {
+                    Locked { pin: self }
+                }
+            }
+
+            impl<MODE> Lockable for $PXx<MODE> {
+                fn mask(&self) -> u16 {
+                    1 << self.i
+                }
+            }
+
+            impl<A: Lockable, B: Lockable> Lockable for (A, B) {
+                fn mask(&self) -> u16 {
+                    self.0.mask() | self.1.mask()
+                }
+            }
+
+            impl<A: Lockable, B: Lockable, C: Lockable> Lockable for (A, B, C) {
+                fn mask(&self) -> u16 {
+                    self.0.mask() | self.1.mask() | self.2.mask()
+                }
+            }
+
+            impl<A: Lockable, B: Lockable, C: Lockable, D: Lockable> Lockable for (A, B, C, D) {
+                fn mask(&self) -> u16 {
+                    self.0.mask() | self.1.mask() | self.2.mask() | self.3.mask()
                 }
             }
 
@@ -162,9 +875,14 @@ macro_rules! gpio {
                 _0: ()
             }
 
-            impl MODER {
-                pub(crate) fn moder(&mut self) -> &$gpioy::MODER {
-                    unsafe { &(*$GPIOX::ptr()).moder }
+            /// Opaque OSPEEDR register
+            pub struct OSPEEDR {
+                _0: ()
+            }
+
+            impl OSPEEDR {
+                pub(crate) fn ospeedr(&mut self) -> &$gpioy::OSPEEDR {
+                    unsafe { &(*ptr()).ospeedr }
                 }
             }
 
@@ -173,38 +891,202 @@ macro_rules! gpio {
                 _0: ()
             }
 
-            impl OTYPER {
-                pub(crate) fn otyper(&mut self) -> &$gpioy::OTYPER {
-                    unsafe { &(*$GPIOX::ptr()).otyper }
-                }
-            }
-
             /// Opaque PUPDR register
             pub struct PUPDR {
                 _0: ()
             }
 
-            impl PUPDR {
-                pub(crate) fn pupdr(&mut self) -> &$gpioy::PUPDR {
-                    unsafe { &(*$GPIOX::ptr()).pupdr }
-                }
-            }
-
             /// Partially erased pin
             pub struct $PXx<MODE> {
                 i: u8,
                 _mode: PhantomData<MODE>,
             }
 
+            impl<MODE> $PXx<MODE> {
+                /// Erases the port from the type
+                ///
+                /// This is useful when you want to collect pins of different ports into an array
+                pub fn downgrade(self) -> Pin<MODE> {
+                    Pin {
+                        port: $port_id,
+                        i: self.i,
+                        _mode: self._mode,
+                    }
+                }
+
+                /// Configures the pin to operate in mode `M`
+                pub fn into_mode<M: PinMode>(self) -> $PXx<M> {
+                    super::Config::of::<M>().write($port_id, self.i);
+
+                    $PXx {
+                        i: self.i,
+                        _mode: PhantomData,
+                    }
+                }
+
+                /// Temporarily configures the pin to operate in mode `M`
+                ///
+                /// The previous configuration is restored when `f` returns.
+                pub fn with_mode<M: PinMode, R>(&mut self, f: impl FnOnce(&mut $PXx<M>) -> R) -> R {
+                    let previous = super::Config::read($port_id, self.i);
+                    super::Config::of::<M>().write($port_id, self.i);
+
+                    let r = f(&mut $PXx {
+                        i: self.i,
+                        _mode: PhantomData,
+                    });
+
+                    previous.write($port_id, self.i);
+                    r
+                }
+            }
+
+            impl<MODE> InputPin for $PXx<Input<MODE>> {
+                type Error = Infallible;
+
+                fn is_high(&self) -> Result<bool, Self::Error> {
+                    self.is_low().map(|b| !b)
+                }
+
+                fn is_low(&self) -> Result<bool, Self::Error> {
+                    // NOTE(unsafe) atomic read with no side effects
+                    Ok(unsafe { (*ptr()).idr.read().bits() & (1 << self.i) == 0 })
+                }
+            }
+
+            /// Reads the level of the line, which other devices may pull low
+            impl InputPin for $PXx<Output<OpenDrain>> {
+                type Error = Infallible;
+
+                fn is_high(&self) -> Result<bool, Self::Error> {
+                    self.is_low().map(|b| !b)
+                }
+
+                fn is_low(&self) -> Result<bool, Self::Error> {
+                    // NOTE(unsafe) atomic read with no side effects
+                    Ok(unsafe { (*ptr()).idr.read().bits() & (1 << self.i) == 0 })
+                }
+            }
+
+            impl<MODE> ExtiPin for $PXx<Input<MODE>> {
+                fn make_interrupt_source(&mut self, syscfg: &mut SysCfg) {
+                    super::exti_make_source(syscfg, $port_id, self.i)
+                }
+
+                fn trigger_on_edge(&mut self, exti: &mut EXTI, edge: Edge) {
+                    super::exti_trigger_on_edge(exti, self.i, edge)
+                }
+
+                fn enable_interrupt(&mut self, exti: &mut EXTI) {
+                    super::exti_enable_interrupt(exti, self.i)
+                }
+
+                fn disable_interrupt(&mut self, exti: &mut EXTI) {
+                    super::exti_disable_interrupt(exti, self.i)
+                }
+
+                fn clear_interrupt_pending_bit(&mut self) {
+                    super::exti_clear_interrupt_pending_bit(self.i)
+                }
+
+                fn check_interrupt(&self) -> bool {
+                    super::exti_check_interrupt(self.i)
+                }
+            }
+
             impl<MODE> OutputPin for $PXx<Output<MODE>> {
-                fn set_high(&mut self) {
+                type Error = Infallible;
+
+                fn set_high(&mut self) -> Result<(), Self::Error> {
                     // NOTE(unsafe) atomic write to a stateless register
-                    unsafe { (*$GPIOX::ptr()).bsrr.write(|w| w.bits(1 << self.i)) }
+                    unsafe { (*ptr()).bsrr.write(|w| w.bits(1 << self.i)) };
+                    Ok(())
                 }
 
-                fn set_low(&mut self) {
+                fn set_low(&mut self) -> Result<(), Self::Error> {
                     // NOTE(unsafe) atomic write to a stateless register
-                    unsafe { (*$GPIOX::ptr()).bsrr.write(|w| w.bits(1 << (16 + self.i))) }
+                    unsafe { (*ptr()).bsrr.write(|w| w.bits(1 << (16 + self.i))) };
+                    Ok(())
+                }
+            }
+
+            impl<MODE> StatefulOutputPin for $PXx<Output<MODE>> {
+                fn is_set_high(&self) -> Result<bool, Self::Error> {
+                    self.is_set_low().map(|b| !b)
+                }
+
+                fn is_set_low(&self) -> Result<bool, Self::Error> {
+                    // NOTE(unsafe) atomic read with no side effects
+                    Ok(unsafe { (*ptr()).odr.read().bits() & (1 << self.i) == 0 })
+                }
+            }
+
+            impl<MODE> ToggleableOutputPin for $PXx<Output<MODE>> {
+                type Error = Infallible;
+
+                fn toggle(&mut self) -> Result<(), Self::Error> {
+                    let i = if self.is_set_low()? { self.i } else { 16 + self.i };
+                    // NOTE(unsafe) atomic write to a stateless register
+                    unsafe { (*ptr()).bsrr.write(|w| w.bits(1 << i)) };
+                    Ok(())
+                }
+            }
+
+            /// Group of pins of this port accessed together
+            ///
+            /// Bit `n` of the values read and written is pin `n` of the port. Pins outside the
+            /// group are never modified.
+            pub struct PinGroup<MODE> {
+                mask: u16,
+                _mode: PhantomData<MODE>,
+            }
+
+            impl<MODE> PinGroup<MODE> {
+                /// Creates an empty group
+                pub fn new() -> Self {
+                    PinGroup {
+                        mask: 0,
+                        _mode: PhantomData,
+                    }
+                }
+
+                /// Moves a pin into the group
+                pub fn add(&mut self, pin: $PXx<MODE>) {
+                    self.mask |= 1 << pin.i;
+                }
+
+                /// Returns the pins of the group as a bit mask
+                pub fn mask(&self) -> u16 {
+                    self.mask
+                }
+            }
+
+            impl<MODE> Default for PinGroup<MODE> {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl<MODE> PinGroup<Output<MODE>> {
+                /// Drives all the pins of the group at once
+                ///
+                /// Pins whose bit is set in `value` are driven high, the others low.
+                pub fn write(&mut self, value: u16) {
+                    let set = u32::from(value & self.mask);
+                    let reset = u32::from(!value & self.mask);
+                    // NOTE(unsafe) atomic write to a stateless register
+                    unsafe { (*ptr()).bsrr.write(|w| w.bits(set | (reset << 16))) }
+                }
+            }
+
+            impl<MODE> PinGroup<Input<MODE>> {
+                /// Samples all the pins of the group at once
+                ///
+                /// The bits of the pins outside the group are zero.
+                pub fn read(&self) -> u16 {
+                    // NOTE(unsafe) atomic read with no side effects
+                    let idr = unsafe { (*ptr()).idr.read().bits() };
+                    idr as u16 & self.mask
                 }
             }
 
@@ -215,128 +1097,351 @@ macro_rules! gpio {
                 }
 
                 impl<MODE> $PXi<MODE> {
-                    // TODO: Alternate function implementations
+                    alternate_functions!($PXi, $AFR, [
+                        AF0: (into_alternate_af0, into_alternate_af0_open_drain),
+                        AF1: (into_alternate_af1, into_alternate_af1_open_drain),
+                        AF2: (into_alternate_af2, into_alternate_af2_open_drain),
+                        AF3: (into_alternate_af3, into_alternate_af3_open_drain),
+                        AF4: (into_alternate_af4, into_alternate_af4_open_drain),
+                        AF5: (into_alternate_af5, into_alternate_af5_open_drain),
+                        AF6: (into_alternate_af6, into_alternate_af6_open_drain),
+                        AF7: (into_alternate_af7, into_alternate_af7_open_drain),
+                        AF8: (into_alternate_af8, into_alternate_af8_open_drain),
+                        AF9: (into_alternate_af9, into_alternate_af9_open_drain),
+                        AF10: (into_alternate_af10, into_alternate_af10_open_drain),
+                        AF11: (into_alternate_af11, into_alternate_af11_open_drain),
+                        AF12: (into_alternate_af12, into_alternate_af12_open_drain),
+                        AF13: (into_alternate_af13, into_alternate_af13_open_drain),
+                        AF14: (into_alternate_af14, into_alternate_af14_open_drain),
+                        AF15: (into_alternate_af15, into_alternate_af15_open_drain),
+                    ]);
 
                     /// Configures the pin to operate as a floating point input
-                    pub fn into_floating_input(self, moder: &mut MODER, pupdr: &mut PUPDR) -> $PXi<Input<Floating>> {
-                        let offset = 2 * $i;
+                    pub fn into_floating_input(self, _moder: &mut MODER, _pupdr: &mut PUPDR) -> $PXi<Input<Floating>> {
+                        self.into_mode()
+                    }
 
-                        // Input mode
-                        moder.moder().modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << offset)) });
+                    /// Configures the pin to operate as a pulled-down input
+                    pub fn into_pull_down_input(self, _moder: &mut MODER, _pupdr: &mut PUPDR) -> $PXi<Input<PullDown>> {
+                        self.into_mode()
+                    }
 
-                        // No pull-up or pull-down
-                        pupdr.pupdr().modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << offset)) });
+                    /// Configures the pin to operate as a pulled-up input
+                    pub fn into_pull_up_input(self, _moder: &mut MODER, _pupdr: &mut PUPDR) -> $PXi<Input<PullUp>> {
+                        self.into_mode()
+                    }
 
-                        $PXi { _mode: PhantomData }
+                    /// Configures the pin to operate as an analog pin
+                    pub fn into_analog(self, _moder: &mut MODER, _pupdr: &mut PUPDR) -> $PXi<Analog> {
+                        self.into_mode()
                     }
 
-                    /// Configures the pin to operate as a pulled-down input
-                    pub fn into_pull_down_input(self, moder: &mut MODER, pupdr: &mut PUPDR) -> $PXi<Input<PullDown>> {
-                        let offset = 2 * $i;
+                    /// Configures the pin to operate as an open-drain output
+                    pub fn into_open_drain_output(self, _moder: &mut MODER, _otyper: &mut OTYPER) -> $PXi<Output<OpenDrain>> {
+                        self.into_mode()
+                    }
 
-                        // Input mode
-                        moder.moder().modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << offset)) });
+                    /// Configures the pin to operate as a push-pull output pin
+                    pub fn into_push_pull_output(self, _moder: &mut MODER, _otyper: &mut OTYPER) -> $PXi<Output<PushPull>> {
+                        self.into_mode()
+                    }
 
-                        // Pull down
-                        pupdr.pupdr().modify(|r, w| unsafe { w.bits(r.bits() & !(0b10 << offset)) });
+                    /// Configures the pin to operate in mode `M`
+                    ///
+                    /// Unlike the `into_*` methods this doesn't need the register proxies, the
+                    /// registers are updated in a critical section.
+                    pub fn into_mode<M: PinMode>(self) -> $PXi<M> {
+                        super::Config::of::<M>().write($port_id, $i);
 
                         $PXi { _mode: PhantomData }
                     }
 
-                    /// Configures the pin to operate as a pulled-up input
-                    pub fn into_pull_up_input(self, moder: &mut MODER, pupdr: &mut PUPDR) -> $PXi<Input<PullUp>> {
-                        let offset = 2 * $i;
+                    /// Temporarily configures the pin to operate in mode `M`
+                    ///
+                    /// The previous configuration is restored when `f` returns.
+                    pub fn with_mode<M: PinMode, R>(&mut self, f: impl FnOnce(&mut $PXi<M>) -> R) -> R {
+                        let previous = super::Config::read($port_id, $i);
+                        super::Config::of::<M>().write($port_id, $i);
 
-                        // Input mode
-                        moder.moder().modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << offset)) });
+                        let r = f(&mut $PXi { _mode: PhantomData });
 
-                        // Pull up
-                        pupdr.pupdr().modify(|r, w| unsafe { w.bits(r.bits() & !(0b01 << offset)) });
+                        previous.write($port_id, $i);
+                        r
+                    }
+                }
 
-                        $PXi { _mode: PhantomData }
+                impl $PXi<Output<OpenDrain>> {
+                    /// Enables / disables the internal pull-up
+                    pub fn internal_pull_up(&mut self, _pupdr: &mut PUPDR, on: bool) {
+                        let pupdr = if on { 0b01 } else { 0b00 };
+                        super::Config {
+                            pupdr: Some(pupdr),
+                            ..super::Config::UNCHANGED
+                        }
+                        .write($port_id, $i);
+                    }
+                }
+
+                impl<MODE> Lock for $PXi<MODE> {
+                    type Locked = Locked<Self>;
+
+                    fn lock(self, _key: &LockKey) -> Self::Locked {
+                        Locked { pin: self }
                     }
+                }
 
-                    /// Configures the pin to operate as an open-drain output
-                    pub fn into_open_drain_output(self, moder: &mut MODER, otyper: &mut OTYPER) -> $PXi<Output<OpenDrain>> {
-                        let offset = 2 * $i;
+                impl<MODE> Lockable for $PXi<MODE> {
+                    fn mask(&self) -> u16 {
+                        1 << $i
+                    }
+                }
 
-                        // General purpose output mode
-                        let mode = 0b01;
-                        moder.moder().modify(|r, w| unsafe {
-                            w.bits((r.bits() & !(0b11 << offset)) | (mode << offset))
-                        });
+                impl $PXi<Dynamic> {
+                    /// Configures the pin to operate as a floating input
+                    pub fn make_floating_input(&mut self, _moder: &mut MODER, _pupdr: &mut PUPDR) {
+                        self.make_mode::<Input<Floating>>()
+                    }
 
-                        // Open-drain output
-                        otyper.otyper().modify(|r, w| unsafe { w.bits(r.bits() | (0b1 << $i))});
+                    /// Configures the pin to operate as a pulled-down input
+                    pub fn make_pull_down_input(&mut self, _moder: &mut MODER, _pupdr: &mut PUPDR) {
+                        self.make_mode::<Input<PullDown>>()
+                    }
 
-                        $PXi { _mode: PhantomData }
+                    /// Configures the pin to operate as a pulled-up input
+                    pub fn make_pull_up_input(&mut self, _moder: &mut MODER, _pupdr: &mut PUPDR) {
+                        self.make_mode::<Input<PullUp>>()
                     }
 
-                    /// Configures the pin to operate as a push-pull output pin
-                    pub fn into_push_pull_output(self, moder: &mut MODER, otyper: &mut OTYPER) -> $PXi<Output<PushPull>> {
+                    /// Configures the pin to operate as an open-drain output
+                    pub fn make_open_drain_output(&mut self, _moder: &mut MODER, _otyper: &mut OTYPER) {
+                        self.make_mode::<Output<OpenDrain>>()
+                    }
+
+                    /// Configures the pin to operate as a push-pull output
+                    pub fn make_push_pull_output(&mut self, _moder: &mut MODER, _otyper: &mut OTYPER) {
+                        self.make_mode::<Output<PushPull>>()
+                    }
+
+                    /// Configures the pin to operate in mode `M` without the register proxies
+                    pub fn make_mode<M: PinMode>(&mut self) {
+                        super::Config::of::<M>().write($port_id, $i)
+                    }
+
+                    /// Returns the current configuration of the pin
+                    pub fn mode(&self) -> DynamicMode {
                         let offset = 2 * $i;
 
-                        // General purpose output mode
-                        let mode = 0b01;
-                        moder.moder().modify(|r, w| unsafe {
-                            w.bits((r.bits() & !(0b11 << offset)) | (mode << offset))
-                        });
+                        // NOTE(unsafe) atomic reads with no side effects
+                        let (moder, otyper, pupdr) = unsafe {
+                            let gpio = &*ptr();
+                            (
+                                (gpio.moder.read().bits() >> offset) & 0b11,
+                                (gpio.otyper.read().bits() >> $i) & 0b1,
+                                (gpio.pupdr.read().bits() >> offset) & 0b11,
+                            )
+                        };
 
-                        // Push-pull output
-                        otyper.otyper().modify(|r, w| unsafe { w.bits(r.bits() & !(0b1 << $i)) });
+                        match (moder, otyper, pupdr) {
+                            (0b00, _, 0b01) => DynamicMode::PullUpInput,
+                            (0b00, _, 0b10) => DynamicMode::PullDownInput,
+                            (0b00, _, _) => DynamicMode::FloatingInput,
+                            (0b01, 0b0, _) => DynamicMode::PushPullOutput,
+                            (0b01, _, _) => DynamicMode::OpenDrainOutput,
+                            _ => DynamicMode::Other,
+                        }
+                    }
 
-                        $PXi { _mode: PhantomData }
+                    fn write(&mut self, bits: u32) -> Result<(), PinModeError> {
+                        match self.mode() {
+                            DynamicMode::PushPullOutput | DynamicMode::OpenDrainOutput => {
+                                // NOTE(unsafe) atomic write to a stateless register
+                                unsafe { (*ptr()).bsrr.write(|w| w.bits(bits)) }
+                                Ok(())
+                            }
+                            _ => Err(PinModeError::IncorrectMode),
+                        }
                     }
                 }
 
-                impl $PXi<Output<OpenDrain>> {
-                    /// Enables / disables the internal pull-up
-                    pub fn internal_pull_up(&mut self, pupdr: &mut PUPDR, on: bool) {
-                        let offset = 2 * $i;
+                /// Fails if the pin is not an output
+                impl OutputPin for $PXi<Dynamic> {
+                    type Error = PinModeError;
 
-                        pupdr.pupdr().modify(|r, w| unsafe {
-                            w.bits((r.bits() & !(0b11 << offset)) | if on {
-                                0b01 << offset
-                            } else {
-                                0
-                            })
-                        });
+                    fn set_high(&mut self) -> Result<(), Self::Error> {
+                        self.write(1 << $i)
+                    }
+
+                    fn set_low(&mut self) -> Result<(), Self::Error> {
+                        self.write(1 << (16 + $i))
                     }
                 }
 
-                impl<MODE> $PXi<Output<MODE>> {
+                /// Fails if the pin is neither an input nor an open-drain output
+                impl InputPin for $PXi<Dynamic> {
+                    type Error = PinModeError;
+
+                    fn is_high(&self) -> Result<bool, Self::Error> {
+                        self.is_low().map(|b| !b)
+                    }
+
+                    fn is_low(&self) -> Result<bool, Self::Error> {
+                        match self.mode() {
+                            DynamicMode::PushPullOutput | DynamicMode::Other => {
+                                Err(PinModeError::IncorrectMode)
+                            }
+                            // NOTE(unsafe) atomic read with no side effects
+                            _ => Ok(unsafe { (*ptr()).idr.read().bits() & (1 << $i) == 0 }),
+                        }
+                    }
+                }
+
+                impl<MODE> $PXi<MODE> {
                     /// Erases the pin number from the type
                     ///
                     /// This is useful when you want to collect the pins into an array where you
                     /// need all the elements to have the same type
-                    pub fn downgrade(self) -> $PXx<Output<MODE>> {
+                    pub fn downgrade(self) -> $PXx<MODE> {
                         $PXx {
                             i: $i,
                             _mode: self._mode,
                         }
                     }
+
+                    /// Turns the pin into a dynamic pin, keeping its current configuration
+                    pub fn into_dynamic(self) -> $PXi<Dynamic> {
+                        $PXi { _mode: PhantomData }
+                    }
+
+                    fn speed(&mut self, ospeedr: &mut OSPEEDR, speed: Speed) {
+                        let bits = match speed {
+                            Speed::Low => 0b00,
+                            Speed::Medium => 0b01,
+                            Speed::High => 0b10,
+                        };
+                        self.speed_bits(ospeedr, bits)
+                    }
+
+                    fn speed_bits(&mut self, ospeedr: &mut OSPEEDR, bits: u32) {
+                        let offset = 2 * $i;
+
+                        ospeedr.ospeedr().modify(|r, w| unsafe {
+                            w.bits((r.bits() & !(0b11 << offset)) | (bits << offset))
+                        });
+                    }
+                }
+
+                impl<MODE> $PXi<Output<MODE>> {
+                    /// Sets the output speed
+                    pub fn set_speed(&mut self, ospeedr: &mut OSPEEDR, speed: Speed) {
+                        self.speed(ospeedr, speed)
+                    }
+
+                    /// Sets the output speed to very high, which requires the I/O compensation cell
+                    pub fn set_very_high_speed(&mut self, ospeedr: &mut OSPEEDR, _cell: &CompensationCell) {
+                        self.speed_bits(ospeedr, 0b11)
+                    }
+                }
+
+                impl<AF, MODE> $PXi<Alternate<AF, MODE>> {
+                    /// Sets the output speed
+                    pub fn set_speed(&mut self, ospeedr: &mut OSPEEDR, speed: Speed) {
+                        self.speed(ospeedr, speed)
+                    }
+
+                    /// Sets the output speed to very high, which requires the I/O compensation cell
+                    pub fn set_very_high_speed(&mut self, ospeedr: &mut OSPEEDR, _cell: &CompensationCell) {
+                        self.speed_bits(ospeedr, 0b11)
+                    }
                 }
 
                 impl<MODE> OutputPin for $PXi<Output<MODE>> {
-                    fn set_high(&mut self) {
+                    type Error = Infallible;
+
+                    fn set_high(&mut self) -> Result<(), Self::Error> {
+                        // NOTE(unsafe) atomic write to a stateless register
+                        unsafe { (*ptr()).bsrr.write(|w| w.bits(1 << $i)) };
+                        Ok(())
+                    }
+
+                    fn set_low(&mut self) -> Result<(), Self::Error> {
                         // NOTE(unsafe) atomic write to a stateless register
-                        unsafe { (*$GPIOX::ptr()).bsrr.write(|w| w.bits(1 << $i)) }
+                        unsafe { (*ptr()).bsrr.write(|w| w.bits(1 << (16 + $i))) };
+                        Ok(())
                     }
+                }
+
+                impl<MODE> StatefulOutputPin for $PXi<Output<MODE>> {
+                    fn is_set_high(&self) -> Result<bool, Self::Error> {
+                        self.is_set_low().map(|b| !b)
+                    }
+
+                    fn is_set_low(&self) -> Result<bool, Self::Error> {
+                        // NOTE(unsafe) atomic read with no side effects
+                        Ok(unsafe { (*ptr()).odr.read().bits() & (1 << $i) == 0 })
+                    }
+                }
 
-                    fn set_low(&mut self) {
+                impl<MODE> ToggleableOutputPin for $PXi<Output<MODE>> {
+                    type Error = Infallible;
+
+                    fn toggle(&mut self) -> Result<(), Self::Error> {
+                        let i = if self.is_set_low()? { $i } else { 16 + $i };
                         // NOTE(unsafe) atomic write to a stateless register
-                        unsafe { (*$GPIOX::ptr()).bsrr.write(|w| w.bits(1 << (16 + $i))) }
+                        unsafe { (*ptr()).bsrr.write(|w| w.bits(1 << i)) };
+                        Ok(())
                     }
                 }
 
                 impl<MODE> InputPin for $PXi<Input<MODE>> {
-                    fn is_high(&self) -> bool {
-                        !self.is_low()
+                    type Error = Infallible;
+
+                    fn is_high(&self) -> Result<bool, Self::Error> {
+                        self.is_low().map(|b| !b)
                     }
 
-                    fn is_low(&self) -> bool {
+                    fn is_low(&self) -> Result<bool, Self::Error> {
                         // NOTE(unsafe) atomic read with no side effects
-                        unsafe { (*$GPIOX::ptr()).idr.read().bits() & (1 << $i) == 0 }
+                        Ok(unsafe { (*ptr()).idr.read().bits() & (1 << $i) == 0 })
+                    }
+                }
+
+                /// Reads the level of the line, which other devices may pull low
+                impl InputPin for $PXi<Output<OpenDrain>> {
+                    type Error = Infallible;
+
+                    fn is_high(&self) -> Result<bool, Self::Error> {
+                        self.is_low().map(|b| !b)
+                    }
+
+                    fn is_low(&self) -> Result<bool, Self::Error> {
+                        // NOTE(unsafe) atomic read with no side effects
+                        Ok(unsafe { (*ptr()).idr.read().bits() & (1 << $i) == 0 })
+                    }
+                }
+
+                impl<MODE> ExtiPin for $PXi<Input<MODE>> {
+                    fn make_interrupt_source(&mut self, syscfg: &mut SysCfg) {
+                        super::exti_make_source(syscfg, $port_id, $i)
+                    }
+
+                    fn trigger_on_edge(&mut self, exti: &mut EXTI, edge: Edge) {
+                        super::exti_trigger_on_edge(exti, $i, edge)
+                    }
+
+                    fn enable_interrupt(&mut self, exti: &mut EXTI) {
+                        super::exti_enable_interrupt(exti, $i)
+                    }
+
+                    fn disable_interrupt(&mut self, exti: &mut EXTI) {
+                        super::exti_disable_interrupt(exti, $i)
+                    }
+
+                    fn clear_interrupt_pending_bit(&mut self) {
+                        super::exti_clear_interrupt_pending_bit($i)
+                    }
+
+                    fn check_interrupt(&self) -> bool {
+                        super::exti_check_interrupt($i)
                     }
                 }
             )+
@@ -344,7 +1449,7 @@ macro_rules! gpio {
     };
 }
 
-gpio!(GPIOA, gpioa, gpioa, gpioaen, gpioarst, PAx, [
+gpio!(GPIOA, gpioa, gpioa, gpioaen, gpioarst, PAx, 0, [
     PA0: (pa0, 0, Input<Floating>, AFRL),
     PA1: (pa1, 1, Input<Floating>, AFRL),
     PA2: (pa2, 2, Input<Floating>, AFRL),
@@ -363,7 +1468,480 @@ gpio!(GPIOA, gpioa, gpioa, gpioaen, gpioarst, PAx, [
     PA15: (pa15, 15, Input<Floating>, AFRH),
 ]);
 
-// SVD says GPIOK is derived from GPIOD
-gpio!(GPIOK, gpiod, gpiod, gpioken, gpiokrst, PKx, [
+gpio!(GPIOB, gpiob, gpiob, gpioben, gpiobrst, PBx, 1, [
+    PB0: (pb0, 0, Input<Floating>, AFRL),
+    PB1: (pb1, 1, Input<Floating>, AFRL),
+    PB2: (pb2, 2, Input<Floating>, AFRL),
+    PB3: (pb3, 3, Input<Floating>, AFRL),
+    PB4: (pb4, 4, Input<Floating>, AFRL),
+    PB5: (pb5, 5, Input<Floating>, AFRL),
+    PB6: (pb6, 6, Input<Floating>, AFRL),
+    PB7: (pb7, 7, Input<Floating>, AFRL),
+    PB8: (pb8, 8, Input<Floating>, AFRH),
+    PB9: (pb9, 9, Input<Floating>, AFRH),
+    PB10: (pb10, 10, Input<Floating>, AFRH),
+    PB11: (pb11, 11, Input<Floating>, AFRH),
+    PB12: (pb12, 12, Input<Floating>, AFRH),
+    PB13: (pb13, 13, Input<Floating>, AFRH),
+    PB14: (pb14, 14, Input<Floating>, AFRH),
+    PB15: (pb15, 15, Input<Floating>, AFRH),
+]);
+
+gpio!(GPIOC, gpioc, gpiod, gpiocen, gpiocrst, PCx, 2, [
+    PC0: (pc0, 0, Input<Floating>, AFRL),
+    PC1: (pc1, 1, Input<Floating>, AFRL),
+    PC2: (pc2, 2, Input<Floating>, AFRL),
+    PC3: (pc3, 3, Input<Floating>, AFRL),
+    PC4: (pc4, 4, Input<Floating>, AFRL),
+    PC5: (pc5, 5, Input<Floating>, AFRL),
+    PC6: (pc6, 6, Input<Floating>, AFRL),
+    PC7: (pc7, 7, Input<Floating>, AFRL),
+    PC8: (pc8, 8, Input<Floating>, AFRH),
+    PC9: (pc9, 9, Input<Floating>, AFRH),
+    PC10: (pc10, 10, Input<Floating>, AFRH),
+    PC11: (pc11, 11, Input<Floating>, AFRH),
+    PC12: (pc12, 12, Input<Floating>, AFRH),
+    PC13: (pc13, 13, Input<Floating>, AFRH),
+    PC14: (pc14, 14, Input<Floating>, AFRH),
+    PC15: (pc15, 15, Input<Floating>, AFRH),
+]);
+
+gpio!(GPIOD, gpiod, gpiod, gpioden, gpiodrst, PDx, 3, [
+    PD0: (pd0, 0, Input<Floating>, AFRL),
+    PD1: (pd1, 1, Input<Floating>, AFRL),
+    PD2: (pd2, 2, Input<Floating>, AFRL),
+    PD3: (pd3, 3, Input<Floating>, AFRL),
+    PD4: (pd4, 4, Input<Floating>, AFRL),
+    PD5: (pd5, 5, Input<Floating>, AFRL),
+    PD6: (pd6, 6, Input<Floating>, AFRL),
+    PD7: (pd7, 7, Input<Floating>, AFRL),
+    PD8: (pd8, 8, Input<Floating>, AFRH),
+    PD9: (pd9, 9, Input<Floating>, AFRH),
+    PD10: (pd10, 10, Input<Floating>, AFRH),
+    PD11: (pd11, 11, Input<Floating>, AFRH),
+    PD12: (pd12, 12, Input<Floating>, AFRH),
+    PD13: (pd13, 13, Input<Floating>, AFRH),
+    PD14: (pd14, 14, Input<Floating>, AFRH),
+    PD15: (pd15, 15, Input<Floating>, AFRH),
+]);
+
+gpio!(GPIOE, gpioe, gpiod, gpioeen, gpioerst, PEx, 4, [
+    PE0: (pe0, 0, Input<Floating>, AFRL),
+    PE1: (pe1, 1, Input<Floating>, AFRL),
+    PE2: (pe2, 2, Input<Floating>, AFRL),
+    PE3: (pe3, 3, Input<Floating>, AFRL),
+    PE4: (pe4, 4, Input<Floating>, AFRL),
+    PE5: (pe5, 5, Input<Floating>, AFRL),
+    PE6: (pe6, 6, Input<Floating>, AFRL),
+    PE7: (pe7, 7, Input<Floating>, AFRL),
+    PE8: (pe8, 8, Input<Floating>, AFRH),
+    PE9: (pe9, 9, Input<Floating>, AFRH),
+    PE10: (pe10, 10, Input<Floating>, AFRH),
+    PE11: (pe11, 11, Input<Floating>, AFRH),
+    PE12: (pe12, 12, Input<Floating>, AFRH),
+    PE13: (pe13, 13, Input<Floating>, AFRH),
+    PE14: (pe14, 14, Input<Floating>, AFRH),
+    PE15: (pe15, 15, Input<Floating>, AFRH),
+]);
+
+gpio!(GPIOF, gpiof, gpiod, gpiofen, gpiofrst, PFx, 5, [
+    PF0: (pf0, 0, Input<Floating>, AFRL),
+    PF1: (pf1, 1, Input<Floating>, AFRL),
+    PF2: (pf2, 2, Input<Floating>, AFRL),
+    PF3: (pf3, 3, Input<Floating>, AFRL),
+    PF4: (pf4, 4, Input<Floating>, AFRL),
+    PF5: (pf5, 5, Input<Floating>, AFRL),
+    PF6: (pf6, 6, Input<Floating>, AFRL),
+    PF7: (pf7, 7, Input<Floating>, AFRL),
+    PF8: (pf8, 8, Input<Floating>, AFRH),
+    PF9: (pf9, 9, Input<Floating>, AFRH),
+    PF10: (pf10, 10, Input<Floating>, AFRH),
+    PF11: (pf11, 11, Input<Floating>, AFRH),
+    PF12: (pf12, 12, Input<Floating>, AFRH),
+    PF13: (pf13, 13, Input<Floating>, AFRH),
+    PF14: (pf14, 14, Input<Floating>, AFRH),
+    PF15: (pf15, 15, Input<Floating>, AFRH),
+]);
+
+gpio!(GPIOG, gpiog, gpiod, gpiogen, gpiogrst, PGx, 6, [
+    PG0: (pg0, 0, Input<Floating>, AFRL),
+    PG1: (pg1, 1, Input<Floating>, AFRL),
+    PG2: (pg2, 2, Input<Floating>, AFRL),
+    PG3: (pg3, 3, Input<Floating>, AFRL),
+    PG4: (pg4, 4, Input<Floating>, AFRL),
+    PG5: (pg5, 5, Input<Floating>, AFRL),
+    PG6: (pg6, 6, Input<Floating>, AFRL),
+    PG7: (pg7, 7, Input<Floating>, AFRL),
+    PG8: (pg8, 8, Input<Floating>, AFRH),
+    PG9: (pg9, 9, Input<Floating>, AFRH),
+    PG10: (pg10, 10, Input<Floating>, AFRH),
+    PG11: (pg11, 11, Input<Floating>, AFRH),
+    PG12: (pg12, 12, Input<Floating>, AFRH),
+    PG13: (pg13, 13, Input<Floating>, AFRH),
+    PG14: (pg14, 14, Input<Floating>, AFRH),
+    PG15: (pg15, 15, Input<Floating>, AFRH),
+]);
+
+gpio!(GPIOH, gpioh, gpiod, gpiohen, gpiohrst, PHx, 7, [
+    PH0: (ph0, 0, Input<Floating>, AFRL),
+    PH1: (ph1, 1, Input<Floating>, AFRL),
+    PH2: (ph2, 2, Input<Floating>, AFRL),
+    PH3: (ph3, 3, Input<Floating>, AFRL),
+    PH4: (ph4, 4, Input<Floating>, AFRL),
+    PH5: (ph5, 5, Input<Floating>, AFRL),
+    PH6: (ph6, 6, Input<Floating>, AFRL),
+    PH7: (ph7, 7, Input<Floating>, AFRL),
+    PH8: (ph8, 8, Input<Floating>, AFRH),
+    PH9: (ph9, 9, Input<Floating>, AFRH),
+    PH10: (ph10, 10, Input<Floating>, AFRH),
+    PH11: (ph11, 11, Input<Floating>, AFRH),
+    PH12: (ph12, 12, Input<Floating>, AFRH),
+    PH13: (ph13, 13, Input<Floating>, AFRH),
+    PH14: (ph14, 14, Input<Floating>, AFRH),
+    PH15: (ph15, 15, Input<Floating>, AFRH),
+]);
+
+#[cfg(any(feature = "stm32f7x2", feature = "stm32f7x3"))]
+gpio!(GPIOI, gpioi, gpiod, gpioien, gpioirst, PIx, 8, [
+    PI0: (pi0, 0, Input<Floating>, AFRL),
+    PI1: (pi1, 1, Input<Floating>, AFRL),
+    PI2: (pi2, 2, Input<Floating>, AFRL),
+    PI3: (pi3, 3, Input<Floating>, AFRL),
+    PI4: (pi4, 4, Input<Floating>, AFRL),
+    PI5: (pi5, 5, Input<Floating>, AFRL),
+    PI6: (pi6, 6, Input<Floating>, AFRL),
+    PI7: (pi7, 7, Input<Floating>, AFRL),
+    PI8: (pi8, 8, Input<Floating>, AFRH),
+    PI9: (pi9, 9, Input<Floating>, AFRH),
+    PI10: (pi10, 10, Input<Floating>, AFRH),
+    PI11: (pi11, 11, Input<Floating>, AFRH),
+]);
+
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+gpio!(GPIOI, gpioi, gpiod, gpioien, gpioirst, PIx, 8, [
+    PI0: (pi0, 0, Input<Floating>, AFRL),
+    PI1: (pi1, 1, Input<Floating>, AFRL),
+    PI2: (pi2, 2, Input<Floating>, AFRL),
+    PI3: (pi3, 3, Input<Floating>, AFRL),
+    PI4: (pi4, 4, Input<Floating>, AFRL),
+    PI5: (pi5, 5, Input<Floating>, AFRL),
+    PI6: (pi6, 6, Input<Floating>, AFRL),
+    PI7: (pi7, 7, Input<Floating>, AFRL),
+    PI8: (pi8, 8, Input<Floating>, AFRH),
+    PI9: (pi9, 9, Input<Floating>, AFRH),
+    PI10: (pi10, 10, Input<Floating>, AFRH),
+    PI11: (pi11, 11, Input<Floating>, AFRH),
+    PI12: (pi12, 12, Input<Floating>, AFRH),
+    PI13: (pi13, 13, Input<Floating>, AFRH),
+    PI14: (pi14, 14, Input<Floating>, AFRH),
+    PI15: (pi15, 15, Input<Floating>, AFRH),
+]);
+
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+gpio!(GPIOJ, gpioj, gpiod, gpiojen, gpiojrst, PJx, 9, [
+    PJ0: (pj0, 0, Input<Floating>, AFRL),
+    PJ1: (pj1, 1, Input<Floating>, AFRL),
+    PJ2: (pj2, 2, Input<Floating>, AFRL),
+    PJ3: (pj3, 3, Input<Floating>, AFRL),
+    PJ4: (pj4, 4, Input<Floating>, AFRL),
+    PJ5: (pj5, 5, Input<Floating>, AFRL),
+    PJ6: (pj6, 6, Input<Floating>, AFRL),
+    PJ7: (pj7, 7, Input<Floating>, AFRL),
+    PJ8: (pj8, 8, Input<Floating>, AFRH),
+    PJ9: (pj9, 9, Input<Floating>, AFRH),
+    PJ10: (pj10, 10, Input<Floating>, AFRH),
+    PJ11: (pj11, 11, Input<Floating>, AFRH),
+    PJ12: (pj12, 12, Input<Floating>, AFRH),
+    PJ13: (pj13, 13, Input<Floating>, AFRH),
+    PJ14: (pj14, 14, Input<Floating>, AFRH),
+    PJ15: (pj15, 15, Input<Floating>, AFRH),
+]);
+
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+gpio!(GPIOK, gpiok, gpiod, gpioken, gpiokrst, PKx, 10, [
+    PK0: (pk0, 0, Input<Floating>, AFRL),
+    PK1: (pk1, 1, Input<Floating>, AFRL),
+    PK2: (pk2, 2, Input<Floating>, AFRL),
     PK3: (pk3, 3, Input<Floating>, AFRL),
+    PK4: (pk4, 4, Input<Floating>, AFRL),
+    PK5: (pk5, 5, Input<Floating>, AFRL),
+    PK6: (pk6, 6, Input<Floating>, AFRL),
+    PK7: (pk7, 7, Input<Floating>, AFRL),
 ]);
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::UnsafeCell;
+    use std::sync::{Mutex, MutexGuard};
+
+    use super::regs::gpioa::RegisterBlock;
+    use super::{
+        gpioa, gpiob, Alternate, Analog, DynamicMode, Input, OpenDrain, Output, PullUp, PushPull,
+        Speed,
+    };
+    use super::{AF5, AF9};
+    use crate::hal::digital::v2::{InputPin, OutputPin};
+
+    const PORTS: usize = 11;
+    const WORDS: usize = 11;
+
+    const MODER: usize = 0;
+    const OTYPER: usize = 1;
+    const OSPEEDR: usize = 2;
+    const PUPDR: usize = 3;
+    const IDR: usize = 4;
+    const BSRR: usize = 6;
+    const LCKR: usize = 7;
+    const AFRL: usize = 8;
+    const AFRH: usize = 9;
+
+    /// Register words standing in for the GPIO ports
+    struct Mock(UnsafeCell<[[u32; WORDS]; PORTS]>);
+
+    unsafe impl Sync for Mock {}
+
+    static MOCK: Mock = Mock(UnsafeCell::new([[0; WORDS]; PORTS]));
+
+    /// Serializes the tests sharing `MOCK`
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    pub(super) fn port_ptr(port: u8) -> *const RegisterBlock {
+        assert!(usize::from(port) < PORTS);
+        unsafe { (MOCK.0.get() as *const u32).add(usize::from(port) * WORDS) as *const _ }
+    }
+
+    /// Takes the mock and fills the registers of every port with `value`
+    fn reset(value: u32) -> MutexGuard<'static, ()> {
+        let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe { *MOCK.0.get() = [[value; WORDS]; PORTS] };
+        guard
+    }
+
+    fn write(port: usize, register: usize, value: u32) {
+        unsafe { (*MOCK.0.get())[port][register] = value };
+    }
+
+    fn read(port: usize, register: usize) -> u32 {
+        unsafe { (*MOCK.0.get())[port][register] }
+    }
+
+    #[test]
+    fn floating_input() {
+        let _mock = reset(0xFFFF_FFFF);
+        let mut gpioa = gpioa::Parts::new();
+
+        gpioa
+            .pa3
+            .into_floating_input(&mut gpioa.moder, &mut gpioa.pupdr);
+
+        assert_eq!(read(0, MODER), !(0b11 << 6));
+        assert_eq!(read(0, PUPDR), !(0b11 << 6));
+        assert_eq!(read(0, OTYPER), 0xFFFF_FFFF);
+    }
+
+    #[test]
+    fn pull_down_input() {
+        let _mock = reset(0x5555_5555);
+        let mut gpioa = gpioa::Parts::new();
+
+        gpioa
+            .pa3
+            .into_pull_down_input(&mut gpioa.moder, &mut gpioa.pupdr);
+
+        assert_eq!(read(0, MODER), 0x5555_5555 & !(0b11 << 6));
+        assert_eq!(read(0, PUPDR), (0x5555_5555 & !(0b11 << 6)) | (0b10 << 6));
+    }
+
+    #[test]
+    fn pull_up_input() {
+        let _mock = reset(0xAAAA_AAAA);
+        let mut gpioa = gpioa::Parts::new();
+
+        gpioa
+            .pa15
+            .into_pull_up_input(&mut gpioa.moder, &mut gpioa.pupdr);
+
+        assert_eq!(read(0, MODER), 0xAAAA_AAAA & !(0b11 << 30));
+        assert_eq!(read(0, PUPDR), (0xAAAA_AAAA & !(0b11 << 30)) | (0b01 << 30));
+    }
+
+    #[test]
+    fn analog() {
+        let _mock = reset(0);
+        let mut gpiob = gpiob::Parts::new();
+
+        gpiob.pb1.into_analog(&mut gpiob.moder, &mut gpiob.pupdr);
+
+        assert_eq!(read(1, MODER), 0b11 << 2);
+        assert_eq!(read(1, PUPDR), 0);
+        assert_eq!(read(0, MODER), 0);
+    }
+
+    #[test]
+    fn push_pull_output() {
+        let _mock = reset(0xFFFF_FFFF);
+        let mut gpiob = gpiob::Parts::new();
+
+        gpiob
+            .pb7
+            .into_push_pull_output(&mut gpiob.moder, &mut gpiob.otyper);
+
+        assert_eq!(read(1, MODER), !(0b10 << 14));
+        assert_eq!(read(1, OTYPER), !(1 << 7));
+    }
+
+    #[test]
+    fn open_drain_output() {
+        let _mock = reset(0);
+        let mut gpiob = gpiob::Parts::new();
+
+        let mut pin = gpiob
+            .pb7
+            .into_open_drain_output(&mut gpiob.moder, &mut gpiob.otyper);
+        pin.internal_pull_up(&mut gpiob.pupdr, true);
+        pin.set_speed(&mut gpiob.ospeedr, Speed::High);
+
+        assert_eq!(read(1, MODER), 0b01 << 14);
+        assert_eq!(read(1, OTYPER), 1 << 7);
+        assert_eq!(read(1, PUPDR), 0b01 << 14);
+        assert_eq!(read(1, OSPEEDR), 0b10 << 14);
+    }
+
+    #[test]
+    fn open_drain_input() {
+        let _mock = reset(0);
+        let gpiob = gpiob::Parts::new();
+        let pin = gpiob.pb5.into_mode::<Output<OpenDrain>>();
+        let erased = gpiob.pb6.into_mode::<Output<OpenDrain>>().downgrade();
+
+        write(1, IDR, 1 << 5);
+
+        assert_eq!(pin.is_high(), Ok(true));
+        assert_eq!(erased.is_low(), Ok(true));
+        assert_eq!(erased.downgrade().is_high(), Ok(false));
+    }
+
+    #[test]
+    fn alternate() {
+        let _mock = reset(0xFFFF_FFFF);
+        let mut gpioa = gpioa::Parts::new();
+
+        gpioa
+            .pa9
+            .into_alternate_af7(&mut gpioa.moder, &mut gpioa.otyper, &mut gpioa.afrh);
+        gpioa.pa2.into_alternate_af12_open_drain(
+            &mut gpioa.moder,
+            &mut gpioa.otyper,
+            &mut gpioa.afrl,
+        );
+
+        assert_eq!(read(0, MODER), !(0b01 << 18) & !(0b01 << 4));
+        assert_eq!(read(0, OTYPER), !(1 << 9));
+        assert_eq!(read(0, AFRH), !(0b1000 << 4));
+        assert_eq!(read(0, AFRL), !(0b0011 << 8));
+    }
+
+    #[test]
+    fn register_free_conversions() {
+        let _mock = reset(0);
+        let gpioa = gpioa::Parts::new();
+
+        gpioa.pa0.into_mode::<Input<PullUp>>();
+        gpioa.pa1.into_mode::<Output<OpenDrain>>();
+        gpioa.pa8.into_mode::<Alternate<AF5, OpenDrain>>();
+
+        assert_eq!(read(0, MODER), (0b01 << 2) | (0b10 << 16));
+        assert_eq!(read(0, PUPDR), 0b01);
+        assert_eq!(read(0, OTYPER), (1 << 1) | (1 << 8));
+        assert_eq!(read(0, AFRH), 5);
+    }
+
+    #[test]
+    fn into_mode() {
+        let _mock = reset(0);
+        let gpiob = gpiob::Parts::new();
+
+        gpiob.pb10.into_mode::<Alternate<AF9, OpenDrain>>();
+        gpiob.pb2.downgrade().into_mode::<Input<PullUp>>();
+        gpiob.pb0.downgrade().downgrade().into_mode::<Analog>();
+
+        assert_eq!(read(1, MODER), (0b10 << 20) | 0b11);
+        assert_eq!(read(1, OTYPER), 1 << 10);
+        assert_eq!(read(1, PUPDR), 0b01 << 4);
+        assert_eq!(read(1, AFRH), 9 << 8);
+        assert_eq!(read(1, AFRL), 0);
+    }
+
+    #[test]
+    fn with_mode() {
+        let _mock = reset(0);
+        let gpioa = gpioa::Parts::new();
+        let mut pin = gpioa.pa6.into_mode::<Alternate<AF5, OpenDrain>>();
+
+        pin.with_mode(|_: &mut gpioa::PA6<Input<PullUp>>| {
+            assert_eq!(read(0, MODER), 0);
+            assert_eq!(read(0, PUPDR), 0b01 << 12);
+        });
+
+        assert_eq!(read(0, MODER), 0b10 << 12);
+        assert_eq!(read(0, OTYPER), 1 << 6);
+        assert_eq!(read(0, PUPDR), 0);
+        assert_eq!(read(0, AFRL), 5 << 24);
+    }
+
+    #[test]
+    fn dynamic() {
+        let _mock = reset(0);
+        let mut gpioa = gpioa::Parts::new();
+
+        let mut pin = gpioa.pa4.into_dynamic();
+        assert_eq!(pin.mode(), DynamicMode::FloatingInput);
+        assert!(pin.set_high().is_err());
+
+        pin.make_open_drain_output(&mut gpioa.moder, &mut gpioa.otyper);
+        assert_eq!(pin.mode(), DynamicMode::OpenDrainOutput);
+        assert_eq!(read(0, MODER), 0b01 << 8);
+        assert_eq!(read(0, OTYPER), 1 << 4);
+        assert!(pin.set_low().is_ok());
+        assert_eq!(read(0, BSRR), 1 << 20);
+
+        pin.make_pull_down_input(&mut gpioa.moder, &mut gpioa.pupdr);
+        assert_eq!(pin.mode(), DynamicMode::PullDownInput);
+        assert_eq!(read(0, MODER), 0);
+        assert_eq!(read(0, PUPDR), 0b10 << 8);
+    }
+
+    #[test]
+    fn pin_group() {
+        let _mock = reset(0);
+        let gpioa = gpioa::Parts::new();
+        let gpiob = gpiob::Parts::new();
+
+        let mut outputs = gpiob::PinGroup::new();
+        outputs.add(gpiob.pb1.into_mode::<Output<PushPull>>().downgrade());
+        outputs.add(gpiob.pb4.into_mode::<Output<PushPull>>().downgrade());
+        outputs.write(0xFF02);
+
+        assert_eq!(read(1, BSRR), (1 << 1) | (1 << (16 + 4)));
+
+        let mut inputs = gpioa::PinGroup::new();
+        inputs.add(gpioa.pa0.into_mode::<Input<PullUp>>().downgrade());
+        inputs.add(gpioa.pa5.into_mode::<Input<PullUp>>().downgrade());
+        write(0, IDR, 0xFFFF_FFC1);
+
+        assert_eq!(inputs.mask(), 0b10_0001);
+        assert_eq!(inputs.read(), 0b00_0001);
+    }
+
+    #[test]
+    fn lock() {
+        let _mock = reset(0);
+        let gpiob = gpiob::Parts::new();
+
+        assert!(gpiob.lckr.lock((gpiob.pb3, gpiob.pb12.downgrade())).is_ok());
+
+        assert_eq!(read(1, LCKR), (1 << 16) | (1 << 12) | (1 << 3));
+    }
+}
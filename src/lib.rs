@@ -45,6 +45,24 @@ pub mod delay;
     feature = "stm32f7x7",
     feature = "stm32f7x9"
 ))]
+pub mod eeprom;
+#[cfg(any(
+    feature = "stm32f7x2",
+    feature = "stm32f7x3",
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
+pub mod exti;
+#[cfg(any(
+    feature = "stm32f7x2",
+    feature = "stm32f7x3",
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
 pub mod flash;
 #[cfg(any(
     feature = "stm32f7x2",
@@ -63,6 +81,15 @@ pub mod gpio;
     feature = "stm32f7x7",
     feature = "stm32f7x9"
 ))]
+pub mod pins;
+#[cfg(any(
+    feature = "stm32f7x2",
+    feature = "stm32f7x3",
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
 pub mod prelude;
 #[cfg(any(
     feature = "stm32f7x2",
@@ -81,4 +108,13 @@ pub mod rcc;
     feature = "stm32f7x7",
     feature = "stm32f7x9"
 ))]
+pub mod syscfg;
+#[cfg(any(
+    feature = "stm32f7x2",
+    feature = "stm32f7x3",
+    feature = "stm32f7x5",
+    feature = "stm32f7x6",
+    feature = "stm32f7x7",
+    feature = "stm32f7x9"
+))]
 pub mod time;
@@ -0,0 +1,801 @@
+//! Peripheral pin mappings
+//!
+//! The marker traits of this module let peripheral constructors reject invalid pins at compile
+//! time. They cover the signals of the datasheet alternate function tables for the U(S)ART,
+//! SPI, I2C, timer, CAN, SDMMC, QUADSPI, Ethernet, FMC, SAI, USB OTG and LTDC peripherals. The
+//! DCMI, SPDIFRX, DFSDM, MDIOS, LPTIM, HDMI-CEC, I2S clock inputs, U(S)ART clock outputs and
+//! SMBus alert signals are not mapped. I2C pins must be configured as open-drain.
+//!
+//! The `stm32f7x5` feature selects the STM32F745 register set, so it gets the STM32F74x/F75x
+//! mappings even on an STM32F765.
+
+use crate::gpio::{gpioa, gpiob, gpioc, gpiod, gpioe, gpiof, gpiog, gpioh, gpioi};
+#[cfg(any(feature = "stm32f7x6", feature = "stm32f7x7", feature = "stm32f7x9"))]
+use crate::gpio::{gpioj, gpiok, AF14};
+use crate::gpio::{Alternate, OpenDrain};
+use crate::gpio::{AF1, AF10, AF11, AF12, AF2, AF3, AF4, AF5, AF6, AF7, AF8, AF9};
+#[cfg(any(feature = "stm32f7x7", feature = "stm32f7x9"))]
+use crate::stm32::CAN3;
+#[cfg(any(feature = "stm32f7x6", feature = "stm32f7x7", feature = "stm32f7x9"))]
+use crate::stm32::LTDC;
+#[cfg(not(feature = "stm32f7x3"))]
+use crate::stm32::OTG_HS_GLOBAL;
+#[cfg(not(any(feature = "stm32f7x5", feature = "stm32f7x6")))]
+use crate::stm32::SDMMC2;
+use crate::stm32::{CAN1, FMC, OTG_FS_GLOBAL, QUADSPI, SAI1, SAI2, SDMMC1};
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+use crate::stm32::{CAN2, ETHERNET_MAC};
+use crate::stm32::{I2C1, I2C2, I2C3};
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+use crate::stm32::{I2C4, SPI6};
+use crate::stm32::{SPI1, SPI2, SPI3, SPI4, SPI5};
+use crate::stm32::{TIM1, TIM10, TIM11, TIM12, TIM13, TIM14, TIM2, TIM3, TIM4, TIM5, TIM8, TIM9};
+use crate::stm32::{UART4, UART5, UART7, UART8, USART1, USART2, USART3, USART6};
+
+/// Transmit pin of a U(S)ART or CAN
+pub trait PinTx<USART> {}
+/// Receive pin of a U(S)ART or CAN
+pub trait PinRx<USART> {}
+/// Clear to send pin of a U(S)ART
+pub trait PinCts<USART> {}
+/// Request to send pin of a U(S)ART
+pub trait PinRts<USART> {}
+
+/// Clock pin of a SPI
+pub trait PinSck<SPI> {}
+/// Master in, slave out pin of a SPI
+pub trait PinMiso<SPI> {}
+/// Master out, slave in pin of a SPI
+pub trait PinMosi<SPI> {}
+/// Slave select pin of a SPI
+pub trait PinNss<SPI> {}
+
+/// Clock pin of an I2C
+pub trait PinScl<I2C> {}
+/// Data pin of an I2C
+pub trait PinSda<I2C> {}
+
+/// Channel 1 pin of a timer
+pub trait PinCh1<TIM> {}
+/// Channel 2 pin of a timer
+pub trait PinCh2<TIM> {}
+/// Channel 3 pin of a timer
+pub trait PinCh3<TIM> {}
+/// Channel 4 pin of a timer
+pub trait PinCh4<TIM> {}
+/// Complementary channel 1 pin of an advanced-control timer
+pub trait PinCh1N<TIM> {}
+/// Complementary channel 2 pin of an advanced-control timer
+pub trait PinCh2N<TIM> {}
+/// Complementary channel 3 pin of an advanced-control timer
+pub trait PinCh3N<TIM> {}
+/// External trigger pin of a timer
+pub trait PinEtr<TIM> {}
+/// Break input pin of an advanced-control timer
+pub trait PinBkin<TIM> {}
+/// Second break input pin of an advanced-control timer
+pub trait PinBkin2<TIM> {}
+
+/// Clock pin of a SDMMC, QUADSPI, FMC, LTDC or ULPI interface
+pub trait PinClk<PER> {}
+/// Command pin of a SDMMC
+pub trait PinCmd<PER> {}
+/// Data line 0 of a SDMMC, FMC or ULPI interface
+pub trait PinD0<PER> {}
+/// Data line 1 of a SDMMC, FMC or ULPI interface
+pub trait PinD1<PER> {}
+/// Data line 2 of a SDMMC, FMC or ULPI interface
+pub trait PinD2<PER> {}
+/// Data line 3 of a SDMMC, FMC or ULPI interface
+pub trait PinD3<PER> {}
+/// Data line 4 of a SDMMC, FMC or ULPI interface
+pub trait PinD4<PER> {}
+/// Data line 5 of a SDMMC, FMC or ULPI interface
+pub trait PinD5<PER> {}
+/// Data line 6 of a SDMMC, FMC or ULPI interface
+pub trait PinD6<PER> {}
+/// Data line 7 of a SDMMC, FMC or ULPI interface
+pub trait PinD7<PER> {}
+/// Data line 8 of a FMC
+pub trait PinD8<PER> {}
+/// Data line 9 of a FMC
+pub trait PinD9<PER> {}
+/// Data line 10 of a FMC
+pub trait PinD10<PER> {}
+/// Data line 11 of a FMC
+pub trait PinD11<PER> {}
+/// Data line 12 of a FMC
+pub trait PinD12<PER> {}
+/// Data line 13 of a FMC
+pub trait PinD13<PER> {}
+/// Data line 14 of a FMC
+pub trait PinD14<PER> {}
+/// Data line 15 of a FMC
+pub trait PinD15<PER> {}
+/// Data line 16 of a FMC
+pub trait PinD16<PER> {}
+/// Data line 17 of a FMC
+pub trait PinD17<PER> {}
+/// Data line 18 of a FMC
+pub trait PinD18<PER> {}
+/// Data line 19 of a FMC
+pub trait PinD19<PER> {}
+/// Data line 20 of a FMC
+pub trait PinD20<PER> {}
+/// Data line 21 of a FMC
+pub trait PinD21<PER> {}
+/// Data line 22 of a FMC
+pub trait PinD22<PER> {}
+/// Data line 23 of a FMC
+pub trait PinD23<PER> {}
+/// Data line 24 of a FMC
+pub trait PinD24<PER> {}
+/// Data line 25 of a FMC
+pub trait PinD25<PER> {}
+/// Data line 26 of a FMC
+pub trait PinD26<PER> {}
+/// Data line 27 of a FMC
+pub trait PinD27<PER> {}
+/// Data line 28 of a FMC
+pub trait PinD28<PER> {}
+/// Data line 29 of a FMC
+pub trait PinD29<PER> {}
+/// Data line 30 of a FMC
+pub trait PinD30<PER> {}
+/// Data line 31 of a FMC
+pub trait PinD31<PER> {}
+
+/// Bank 1 chip select pin of a QUADSPI
+pub trait PinBk1Ncs<PER> {}
+/// Bank 1 data line 0 of a QUADSPI
+pub trait PinBk1Io0<PER> {}
+/// Bank 1 data line 1 of a QUADSPI
+pub trait PinBk1Io1<PER> {}
+/// Bank 1 data line 2 of a QUADSPI
+pub trait PinBk1Io2<PER> {}
+/// Bank 1 data line 3 of a QUADSPI
+pub trait PinBk1Io3<PER> {}
+/// Bank 2 chip select pin of a QUADSPI
+pub trait PinBk2Ncs<PER> {}
+/// Bank 2 data line 0 of a QUADSPI
+pub trait PinBk2Io0<PER> {}
+/// Bank 2 data line 1 of a QUADSPI
+pub trait PinBk2Io1<PER> {}
+/// Bank 2 data line 2 of a QUADSPI
+pub trait PinBk2Io2<PER> {}
+/// Bank 2 data line 3 of a QUADSPI
+pub trait PinBk2Io3<PER> {}
+
+/// Management data clock pin of an Ethernet MAC
+pub trait PinMdc<PER> {}
+/// Management data input/output pin of an Ethernet MAC
+pub trait PinMdio<PER> {}
+/// RMII reference clock or MII receive clock pin of an Ethernet MAC
+pub trait PinRefClk<PER> {}
+/// MII transmit clock pin of an Ethernet MAC
+pub trait PinTxClk<PER> {}
+/// RMII carrier sense/data valid or MII receive data valid pin of an Ethernet MAC
+pub trait PinCrsDv<PER> {}
+/// Receive data line 0 of an Ethernet MAC
+pub trait PinRxd0<PER> {}
+/// Receive data line 1 of an Ethernet MAC
+pub trait PinRxd1<PER> {}
+/// Receive data line 2 of an Ethernet MAC (2 and 3 are MII only)
+pub trait PinRxd2<PER> {}
+/// Receive data line 3 of an Ethernet MAC (2 and 3 are MII only)
+pub trait PinRxd3<PER> {}
+/// Transmit data line 0 of an Ethernet MAC
+pub trait PinTxd0<PER> {}
+/// Transmit data line 1 of an Ethernet MAC
+pub trait PinTxd1<PER> {}
+/// Transmit data line 2 of an Ethernet MAC (2 and 3 are MII only)
+pub trait PinTxd2<PER> {}
+/// Transmit data line 3 of an Ethernet MAC (2 and 3 are MII only)
+pub trait PinTxd3<PER> {}
+/// Transmit enable pin of an Ethernet MAC
+pub trait PinTxEn<PER> {}
+/// MII receive error pin of an Ethernet MAC
+pub trait PinRxEr<PER> {}
+/// MII carrier sense pin of an Ethernet MAC
+pub trait PinCrs<PER> {}
+/// MII collision detect pin of an Ethernet MAC
+pub trait PinCol<PER> {}
+/// PPS output pin of an Ethernet MAC
+pub trait PinPpsOut<PER> {}
+
+/// Address line 0 of a FMC
+pub trait PinA0<PER> {}
+/// Address line 1 of a FMC
+pub trait PinA1<PER> {}
+/// Address line 2 of a FMC
+pub trait PinA2<PER> {}
+/// Address line 3 of a FMC
+pub trait PinA3<PER> {}
+/// Address line 4 of a FMC
+pub trait PinA4<PER> {}
+/// Address line 5 of a FMC
+pub trait PinA5<PER> {}
+/// Address line 6 of a FMC
+pub trait PinA6<PER> {}
+/// Address line 7 of a FMC
+pub trait PinA7<PER> {}
+/// Address line 8 of a FMC
+pub trait PinA8<PER> {}
+/// Address line 9 of a FMC
+pub trait PinA9<PER> {}
+/// Address line 10 of a FMC
+pub trait PinA10<PER> {}
+/// Address line 11 of a FMC
+pub trait PinA11<PER> {}
+/// Address line 12 of a FMC
+pub trait PinA12<PER> {}
+/// Address line 13 of a FMC
+pub trait PinA13<PER> {}
+/// Address line 14 of a FMC, also the SDRAM bank address 0
+pub trait PinA14<PER> {}
+/// Address line 15 of a FMC, also the SDRAM bank address 1
+pub trait PinA15<PER> {}
+/// Address line 16 of a FMC
+pub trait PinA16<PER> {}
+/// Address line 17 of a FMC
+pub trait PinA17<PER> {}
+/// Address line 18 of a FMC
+pub trait PinA18<PER> {}
+/// Address line 19 of a FMC
+pub trait PinA19<PER> {}
+/// Address line 20 of a FMC
+pub trait PinA20<PER> {}
+/// Address line 21 of a FMC
+pub trait PinA21<PER> {}
+/// Address line 22 of a FMC
+pub trait PinA22<PER> {}
+/// Address line 23 of a FMC
+pub trait PinA23<PER> {}
+/// Address line 24 of a FMC
+pub trait PinA24<PER> {}
+/// Address line 25 of a FMC
+pub trait PinA25<PER> {}
+/// Byte lane 0 enable pin of a FMC
+pub trait PinNbl0<PER> {}
+/// Byte lane 1 enable pin of a FMC
+pub trait PinNbl1<PER> {}
+/// Byte lane 2 enable pin of a FMC
+pub trait PinNbl2<PER> {}
+/// Byte lane 3 enable pin of a FMC
+pub trait PinNbl3<PER> {}
+/// Chip select 1 pin of a FMC
+pub trait PinNe1<PER> {}
+/// Chip select 2 pin of a FMC
+pub trait PinNe2<PER> {}
+/// Chip select 3 pin of a FMC
+pub trait PinNe3<PER> {}
+/// Chip select 4 pin of a FMC
+pub trait PinNe4<PER> {}
+/// Output enable pin of a FMC
+pub trait PinNoe<PER> {}
+/// Write enable pin of a FMC
+pub trait PinNwe<PER> {}
+/// Wait input pin of a FMC
+pub trait PinNwait<PER> {}
+/// Address valid pin of a FMC
+pub trait PinNl<PER> {}
+/// SDRAM clock pin of a FMC
+pub trait PinSdclk<PER> {}
+/// SDRAM write enable pin of a FMC
+pub trait PinSdnwe<PER> {}
+/// SDRAM row address strobe pin of a FMC
+pub trait PinSdnras<PER> {}
+/// SDRAM column address strobe pin of a FMC
+pub trait PinSdncas<PER> {}
+/// SDRAM bank 1 chip enable pin of a FMC
+pub trait PinSdne0<PER> {}
+/// SDRAM bank 2 chip enable pin of a FMC
+pub trait PinSdne1<PER> {}
+/// SDRAM bank 1 clock enable pin of a FMC
+pub trait PinSdcke0<PER> {}
+/// SDRAM bank 2 clock enable pin of a FMC
+pub trait PinSdcke1<PER> {}
+
+/// Master clock pin of a SAI sub-block A
+pub trait PinMclkA<PER> {}
+/// Bit clock pin of a SAI sub-block A
+pub trait PinSckA<PER> {}
+/// Frame synchronization pin of a SAI sub-block A
+pub trait PinFsA<PER> {}
+/// Serial data pin of a SAI sub-block A
+pub trait PinSdA<PER> {}
+/// Master clock pin of a SAI sub-block B
+pub trait PinMclkB<PER> {}
+/// Bit clock pin of a SAI sub-block B
+pub trait PinSckB<PER> {}
+/// Frame synchronization pin of a SAI sub-block B
+pub trait PinFsB<PER> {}
+/// Serial data pin of a SAI sub-block B
+pub trait PinSdB<PER> {}
+
+/// D- pin of a USB OTG controller
+pub trait PinDm<PER> {}
+/// D+ pin of a USB OTG controller
+pub trait PinDp<PER> {}
+/// ID pin of a USB OTG controller
+pub trait PinId<PER> {}
+/// Start of frame output pin of a USB OTG controller
+pub trait PinSof<PER> {}
+/// ULPI stop pin of a USB OTG controller
+pub trait PinStp<PER> {}
+/// ULPI direction pin of a USB OTG controller
+pub trait PinDir<PER> {}
+/// ULPI next pin of a USB OTG controller
+pub trait PinNxt<PER> {}
+
+/// Red data line 0 of a LTDC
+pub trait PinR0<PER> {}
+/// Red data line 1 of a LTDC
+pub trait PinR1<PER> {}
+/// Red data line 2 of a LTDC
+pub trait PinR2<PER> {}
+/// Red data line 3 of a LTDC
+pub trait PinR3<PER> {}
+/// Red data line 4 of a LTDC
+pub trait PinR4<PER> {}
+/// Red data line 5 of a LTDC
+pub trait PinR5<PER> {}
+/// Red data line 6 of a LTDC
+pub trait PinR6<PER> {}
+/// Red data line 7 of a LTDC
+pub trait PinR7<PER> {}
+/// Green data line 0 of a LTDC
+pub trait PinG0<PER> {}
+/// Green data line 1 of a LTDC
+pub trait PinG1<PER> {}
+/// Green data line 2 of a LTDC
+pub trait PinG2<PER> {}
+/// Green data line 3 of a LTDC
+pub trait PinG3<PER> {}
+/// Green data line 4 of a LTDC
+pub trait PinG4<PER> {}
+/// Green data line 5 of a LTDC
+pub trait PinG5<PER> {}
+/// Green data line 6 of a LTDC
+pub trait PinG6<PER> {}
+/// Green data line 7 of a LTDC
+pub trait PinG7<PER> {}
+/// Blue data line 0 of a LTDC
+pub trait PinB0<PER> {}
+/// Blue data line 1 of a LTDC
+pub trait PinB1<PER> {}
+/// Blue data line 2 of a LTDC
+pub trait PinB2<PER> {}
+/// Blue data line 3 of a LTDC
+pub trait PinB3<PER> {}
+/// Blue data line 4 of a LTDC
+pub trait PinB4<PER> {}
+/// Blue data line 5 of a LTDC
+pub trait PinB5<PER> {}
+/// Blue data line 6 of a LTDC
+pub trait PinB6<PER> {}
+/// Blue data line 7 of a LTDC
+pub trait PinB7<PER> {}
+/// Horizontal synchronization pin of a LTDC
+pub trait PinHsync<PER> {}
+/// Vertical synchronization pin of a LTDC
+pub trait PinVsync<PER> {}
+/// Data enable pin of a LTDC
+pub trait PinDe<PER> {}
+
+macro_rules! pins {
+    ($($Pin:ident<$PER:ident>: [$($gpiox:ident::$PXi:ident: $AF:ident,)+])+) => {
+        $(
+            $(
+                impl<OTYPE> $Pin<$PER> for $gpiox::$PXi<Alternate<$AF, OTYPE>> {}
+            )+
+        )+
+    };
+}
+
+macro_rules! open_drain_pins {
+    ($($Pin:ident<$PER:ident>: [$($gpiox:ident::$PXi:ident: $AF:ident,)+])+) => {
+        $(
+            $(
+                impl $Pin<$PER> for $gpiox::$PXi<Alternate<$AF, OpenDrain>> {}
+            )+
+        )+
+    };
+}
+
+pins! {
+    PinTx<USART1>: [gpioa::PA9: AF7, gpiob::PB6: AF7,]
+    PinRx<USART1>: [gpioa::PA10: AF7, gpiob::PB7: AF7,]
+    PinTx<USART2>: [gpioa::PA2: AF7, gpiod::PD5: AF7,]
+    PinRx<USART2>: [gpioa::PA3: AF7, gpiod::PD6: AF7,]
+    PinTx<USART3>: [gpiob::PB10: AF7, gpioc::PC10: AF7, gpiod::PD8: AF7,]
+    PinRx<USART3>: [gpiob::PB11: AF7, gpioc::PC11: AF7, gpiod::PD9: AF7,]
+    PinTx<UART4>: [gpioa::PA0: AF8, gpioc::PC10: AF8,]
+    PinRx<UART4>: [gpioa::PA1: AF8, gpioc::PC11: AF8,]
+    PinTx<UART5>: [gpioc::PC12: AF8,]
+    PinRx<UART5>: [gpiod::PD2: AF8,]
+    PinTx<USART6>: [gpioc::PC6: AF8, gpiog::PG14: AF8,]
+    PinRx<USART6>: [gpioc::PC7: AF8, gpiog::PG9: AF8,]
+    PinTx<UART7>: [gpioe::PE8: AF8, gpiof::PF7: AF8,]
+    PinRx<UART7>: [gpioe::PE7: AF8, gpiof::PF6: AF8,]
+    PinTx<UART8>: [gpioe::PE1: AF8,]
+    PinRx<UART8>: [gpioe::PE0: AF8,]
+
+    PinSck<SPI1>: [gpioa::PA5: AF5, gpiob::PB3: AF5,]
+    PinMiso<SPI1>: [gpioa::PA6: AF5, gpiob::PB4: AF5,]
+    PinMosi<SPI1>: [gpioa::PA7: AF5, gpiob::PB5: AF5,]
+    PinNss<SPI1>: [gpioa::PA4: AF5, gpioa::PA15: AF5,]
+    PinSck<SPI2>: [
+        gpioa::PA9: AF5, gpiob::PB10: AF5, gpiob::PB13: AF5, gpiod::PD3: AF5, gpioi::PI1: AF5,
+    ]
+    PinMiso<SPI2>: [gpiob::PB14: AF5, gpioc::PC2: AF5, gpioi::PI2: AF5,]
+    PinMosi<SPI2>: [gpiob::PB15: AF5, gpioc::PC1: AF5, gpioc::PC3: AF5, gpioi::PI3: AF5,]
+    PinNss<SPI2>: [gpiob::PB4: AF7, gpiob::PB9: AF5, gpiob::PB12: AF5, gpioi::PI0: AF5,]
+    PinSck<SPI3>: [gpiob::PB3: AF6, gpioc::PC10: AF6,]
+    PinMiso<SPI3>: [gpiob::PB4: AF6, gpioc::PC11: AF6,]
+    PinMosi<SPI3>: [gpiob::PB2: AF7, gpiob::PB5: AF6, gpioc::PC12: AF6, gpiod::PD6: AF5,]
+    PinNss<SPI3>: [gpioa::PA4: AF6, gpioa::PA15: AF6,]
+    PinSck<SPI4>: [gpioe::PE2: AF5, gpioe::PE12: AF5,]
+    PinMiso<SPI4>: [gpioe::PE5: AF5, gpioe::PE13: AF5,]
+    PinMosi<SPI4>: [gpioe::PE6: AF5, gpioe::PE14: AF5,]
+    PinNss<SPI4>: [gpioe::PE4: AF5, gpioe::PE11: AF5,]
+    PinSck<SPI5>: [gpiof::PF7: AF5, gpioh::PH6: AF5,]
+    PinMiso<SPI5>: [gpiof::PF8: AF5, gpioh::PH7: AF5,]
+    PinMosi<SPI5>: [gpiof::PF9: AF5, gpiof::PF11: AF5,]
+    PinNss<SPI5>: [gpiof::PF6: AF5, gpioh::PH5: AF5,]
+
+    PinCh1<TIM1>: [gpioa::PA8: AF1, gpioe::PE9: AF1,]
+    PinCh2<TIM1>: [gpioa::PA9: AF1, gpioe::PE11: AF1,]
+    PinCh3<TIM1>: [gpioa::PA10: AF1, gpioe::PE13: AF1,]
+    PinCh4<TIM1>: [gpioa::PA11: AF1, gpioe::PE14: AF1,]
+    PinCh1<TIM2>: [gpioa::PA0: AF1, gpioa::PA5: AF1, gpioa::PA15: AF1,]
+    PinCh2<TIM2>: [gpioa::PA1: AF1, gpiob::PB3: AF1,]
+    PinCh3<TIM2>: [gpioa::PA2: AF1, gpiob::PB10: AF1,]
+    PinCh4<TIM2>: [gpioa::PA3: AF1, gpiob::PB11: AF1,]
+    PinCh1<TIM3>: [gpioa::PA6: AF2, gpiob::PB4: AF2, gpioc::PC6: AF2,]
+    PinCh2<TIM3>: [gpioa::PA7: AF2, gpiob::PB5: AF2, gpioc::PC7: AF2,]
+    PinCh3<TIM3>: [gpiob::PB0: AF2, gpioc::PC8: AF2,]
+    PinCh4<TIM3>: [gpiob::PB1: AF2, gpioc::PC9: AF2,]
+    PinCh1<TIM4>: [gpiob::PB6: AF2, gpiod::PD12: AF2,]
+    PinCh2<TIM4>: [gpiob::PB7: AF2, gpiod::PD13: AF2,]
+    PinCh3<TIM4>: [gpiob::PB8: AF2, gpiod::PD14: AF2,]
+    PinCh4<TIM4>: [gpiob::PB9: AF2, gpiod::PD15: AF2,]
+    PinCh1<TIM5>: [gpioa::PA0: AF2, gpioh::PH10: AF2,]
+    PinCh2<TIM5>: [gpioa::PA1: AF2, gpioh::PH11: AF2,]
+    PinCh3<TIM5>: [gpioa::PA2: AF2, gpioh::PH12: AF2,]
+    PinCh4<TIM5>: [gpioa::PA3: AF2, gpioi::PI0: AF2,]
+    PinCh1<TIM8>: [gpioc::PC6: AF3, gpioi::PI5: AF3,]
+    PinCh2<TIM8>: [gpioc::PC7: AF3, gpioi::PI6: AF3,]
+    PinCh3<TIM8>: [gpioc::PC8: AF3, gpioi::PI7: AF3,]
+    PinCh4<TIM8>: [gpioc::PC9: AF3, gpioi::PI2: AF3,]
+    PinCh1<TIM9>: [gpioa::PA2: AF3, gpioe::PE5: AF3,]
+    PinCh2<TIM9>: [gpioa::PA3: AF3, gpioe::PE6: AF3,]
+    PinCh1<TIM10>: [gpiob::PB8: AF3, gpiof::PF6: AF3,]
+    PinCh1<TIM11>: [gpiob::PB9: AF3, gpiof::PF7: AF3,]
+    PinCh1<TIM12>: [gpiob::PB14: AF9, gpioh::PH6: AF9,]
+    PinCh2<TIM12>: [gpiob::PB15: AF9, gpioh::PH9: AF9,]
+    PinCh1<TIM13>: [gpioa::PA6: AF9, gpiof::PF8: AF9,]
+    PinCh1<TIM14>: [gpioa::PA7: AF9, gpiof::PF9: AF9,]
+
+    PinCts<USART1>: [gpioa::PA11: AF7,]
+    PinRts<USART1>: [gpioa::PA12: AF7,]
+    PinCts<USART2>: [gpioa::PA0: AF7, gpiod::PD3: AF7,]
+    PinRts<USART2>: [gpioa::PA1: AF7, gpiod::PD4: AF7,]
+    PinCts<USART3>: [gpiob::PB13: AF7, gpiod::PD11: AF7,]
+    PinRts<USART3>: [gpiob::PB14: AF7, gpiod::PD12: AF7,]
+    PinCts<UART4>: [gpiob::PB0: AF8,]
+    PinRts<UART4>: [gpioa::PA15: AF8,]
+    PinCts<UART5>: [gpioc::PC9: AF7,]
+    PinRts<UART5>: [gpioc::PC8: AF7,]
+    PinCts<USART6>: [gpiog::PG13: AF8, gpiog::PG15: AF8,]
+    PinRts<USART6>: [gpiog::PG8: AF8, gpiog::PG12: AF8,]
+    PinCts<UART7>: [gpioe::PE10: AF8, gpiof::PF9: AF8,]
+    PinRts<UART7>: [gpioe::PE9: AF8, gpiof::PF8: AF8,]
+    PinCts<UART8>: [gpiod::PD14: AF8,]
+    PinRts<UART8>: [gpiod::PD15: AF8,]
+
+    PinCh1N<TIM1>: [gpioa::PA7: AF1, gpiob::PB13: AF1, gpioe::PE8: AF1,]
+    PinCh2N<TIM1>: [gpiob::PB0: AF1, gpiob::PB14: AF1, gpioe::PE10: AF1,]
+    PinCh3N<TIM1>: [gpiob::PB1: AF1, gpiob::PB15: AF1, gpioe::PE12: AF1,]
+    PinEtr<TIM1>: [gpioa::PA12: AF1, gpioe::PE7: AF1,]
+    PinBkin<TIM1>: [gpioa::PA6: AF1, gpiob::PB12: AF1, gpioe::PE15: AF1,]
+    PinBkin2<TIM1>: [gpioe::PE6: AF1,]
+    PinEtr<TIM2>: [gpioa::PA0: AF1, gpioa::PA5: AF1, gpioa::PA15: AF1,]
+    PinEtr<TIM3>: [gpiod::PD2: AF2,]
+    PinEtr<TIM4>: [gpioe::PE0: AF2,]
+    PinCh1N<TIM8>: [gpioa::PA5: AF3, gpioa::PA7: AF3, gpioh::PH13: AF3,]
+    PinCh2N<TIM8>: [gpiob::PB0: AF3, gpiob::PB14: AF3, gpioh::PH14: AF3,]
+    PinCh3N<TIM8>: [gpiob::PB1: AF3, gpiob::PB15: AF3, gpioh::PH15: AF3,]
+    PinEtr<TIM8>: [gpioa::PA0: AF3, gpioi::PI3: AF3,]
+    PinBkin<TIM8>: [gpioa::PA6: AF3, gpioi::PI4: AF3,]
+    PinBkin2<TIM8>: [gpioa::PA8: AF3, gpioi::PI1: AF3,]
+
+    PinTx<CAN1>: [gpioa::PA12: AF9, gpiob::PB9: AF9, gpiod::PD1: AF9, gpioh::PH13: AF9,]
+    PinRx<CAN1>: [gpioa::PA11: AF9, gpiob::PB8: AF9, gpiod::PD0: AF9, gpioi::PI9: AF9,]
+
+    PinClk<SDMMC1>: [gpioc::PC12: AF12,]
+    PinCmd<SDMMC1>: [gpiod::PD2: AF12,]
+    PinD0<SDMMC1>: [gpioc::PC8: AF12,]
+    PinD1<SDMMC1>: [gpioc::PC9: AF12,]
+    PinD2<SDMMC1>: [gpioc::PC10: AF12,]
+    PinD3<SDMMC1>: [gpioc::PC11: AF12,]
+    PinD4<SDMMC1>: [gpiob::PB8: AF12,]
+    PinD5<SDMMC1>: [gpiob::PB9: AF12,]
+    PinD6<SDMMC1>: [gpioc::PC6: AF12,]
+    PinD7<SDMMC1>: [gpioc::PC7: AF12,]
+
+    PinClk<QUADSPI>: [gpiob::PB2: AF9, gpiof::PF10: AF9,]
+    PinBk1Ncs<QUADSPI>: [gpiob::PB6: AF10,]
+    PinBk1Io0<QUADSPI>: [gpioc::PC9: AF9, gpiod::PD11: AF9, gpiof::PF8: AF10,]
+    PinBk1Io1<QUADSPI>: [gpioc::PC10: AF9, gpiod::PD12: AF9, gpiof::PF9: AF10,]
+    PinBk1Io2<QUADSPI>: [gpioe::PE2: AF9, gpiof::PF7: AF9,]
+    PinBk1Io3<QUADSPI>: [gpioa::PA1: AF9, gpiod::PD13: AF9, gpiof::PF6: AF9,]
+    PinBk2Ncs<QUADSPI>: [gpioc::PC11: AF9,]
+    PinBk2Io0<QUADSPI>: [gpioe::PE7: AF10, gpioh::PH2: AF9,]
+    PinBk2Io1<QUADSPI>: [gpioe::PE8: AF10, gpioh::PH3: AF9,]
+    PinBk2Io2<QUADSPI>: [gpioe::PE9: AF10, gpiog::PG9: AF9,]
+    PinBk2Io3<QUADSPI>: [gpioe::PE10: AF10, gpiog::PG14: AF9,]
+
+    PinA0<FMC>: [gpiof::PF0: AF12,]
+    PinA1<FMC>: [gpiof::PF1: AF12,]
+    PinA2<FMC>: [gpiof::PF2: AF12,]
+    PinA3<FMC>: [gpiof::PF3: AF12,]
+    PinA4<FMC>: [gpiof::PF4: AF12,]
+    PinA5<FMC>: [gpiof::PF5: AF12,]
+    PinA6<FMC>: [gpiof::PF12: AF12,]
+    PinA7<FMC>: [gpiof::PF13: AF12,]
+    PinA8<FMC>: [gpiof::PF14: AF12,]
+    PinA9<FMC>: [gpiof::PF15: AF12,]
+    PinA10<FMC>: [gpiog::PG0: AF12,]
+    PinA11<FMC>: [gpiog::PG1: AF12,]
+    PinA12<FMC>: [gpiog::PG2: AF12,]
+    PinA13<FMC>: [gpiog::PG3: AF12,]
+    PinA14<FMC>: [gpiog::PG4: AF12,]
+    PinA15<FMC>: [gpiog::PG5: AF12,]
+    PinA16<FMC>: [gpiod::PD11: AF12,]
+    PinA17<FMC>: [gpiod::PD12: AF12,]
+    PinA18<FMC>: [gpiod::PD13: AF12,]
+    PinA19<FMC>: [gpioe::PE3: AF12,]
+    PinA20<FMC>: [gpioe::PE4: AF12,]
+    PinA21<FMC>: [gpioe::PE5: AF12,]
+    PinA22<FMC>: [gpioe::PE6: AF12,]
+    PinA23<FMC>: [gpioe::PE2: AF12,]
+    PinA24<FMC>: [gpiog::PG13: AF12,]
+    PinA25<FMC>: [gpiog::PG14: AF12,]
+    PinD0<FMC>: [gpiod::PD14: AF12,]
+    PinD1<FMC>: [gpiod::PD15: AF12,]
+    PinD2<FMC>: [gpiod::PD0: AF12,]
+    PinD3<FMC>: [gpiod::PD1: AF12,]
+    PinD4<FMC>: [gpioe::PE7: AF12,]
+    PinD5<FMC>: [gpioe::PE8: AF12,]
+    PinD6<FMC>: [gpioe::PE9: AF12,]
+    PinD7<FMC>: [gpioe::PE10: AF12,]
+    PinD8<FMC>: [gpioe::PE11: AF12,]
+    PinD9<FMC>: [gpioe::PE12: AF12,]
+    PinD10<FMC>: [gpioe::PE13: AF12,]
+    PinD11<FMC>: [gpioe::PE14: AF12,]
+    PinD12<FMC>: [gpioe::PE15: AF12,]
+    PinD13<FMC>: [gpiod::PD8: AF12,]
+    PinD14<FMC>: [gpiod::PD9: AF12,]
+    PinD15<FMC>: [gpiod::PD10: AF12,]
+    PinD16<FMC>: [gpioh::PH8: AF12,]
+    PinD17<FMC>: [gpioh::PH9: AF12,]
+    PinD18<FMC>: [gpioh::PH10: AF12,]
+    PinD19<FMC>: [gpioh::PH11: AF12,]
+    PinD20<FMC>: [gpioh::PH12: AF12,]
+    PinD21<FMC>: [gpioh::PH13: AF12,]
+    PinD22<FMC>: [gpioh::PH14: AF12,]
+    PinD23<FMC>: [gpioh::PH15: AF12,]
+    PinD24<FMC>: [gpioi::PI0: AF12,]
+    PinD25<FMC>: [gpioi::PI1: AF12,]
+    PinD26<FMC>: [gpioi::PI2: AF12,]
+    PinD27<FMC>: [gpioi::PI3: AF12,]
+    PinD28<FMC>: [gpioi::PI6: AF12,]
+    PinD29<FMC>: [gpioi::PI7: AF12,]
+    PinD30<FMC>: [gpioi::PI9: AF12,]
+    PinD31<FMC>: [gpioi::PI10: AF12,]
+    PinNbl0<FMC>: [gpioe::PE0: AF12,]
+    PinNbl1<FMC>: [gpioe::PE1: AF12,]
+    PinNbl2<FMC>: [gpioi::PI4: AF12,]
+    PinNbl3<FMC>: [gpioi::PI5: AF12,]
+    PinNe1<FMC>: [gpiod::PD7: AF12,]
+    PinNe2<FMC>: [gpiog::PG9: AF12,]
+    PinNe3<FMC>: [gpiog::PG10: AF12,]
+    PinNe4<FMC>: [gpiog::PG12: AF12,]
+    PinNoe<FMC>: [gpiod::PD4: AF12,]
+    PinNwe<FMC>: [gpiod::PD5: AF12,]
+    PinNwait<FMC>: [gpiod::PD6: AF12,]
+    PinNl<FMC>: [gpiob::PB7: AF12,]
+    PinClk<FMC>: [gpiod::PD3: AF12,]
+    PinSdclk<FMC>: [gpiog::PG8: AF12,]
+    PinSdnwe<FMC>: [gpioc::PC0: AF12, gpioh::PH5: AF12,]
+    PinSdnras<FMC>: [gpiof::PF11: AF12,]
+    PinSdncas<FMC>: [gpiog::PG15: AF12,]
+    PinSdne0<FMC>: [gpioc::PC2: AF12, gpioh::PH3: AF12,]
+    PinSdne1<FMC>: [gpiob::PB6: AF12, gpioh::PH6: AF12,]
+    PinSdcke0<FMC>: [gpioc::PC3: AF12, gpioh::PH2: AF12,]
+    PinSdcke1<FMC>: [gpiob::PB5: AF12, gpioh::PH7: AF12,]
+
+    PinMclkA<SAI1>: [gpioe::PE2: AF6,]
+    PinSckA<SAI1>: [gpioe::PE5: AF6,]
+    PinFsA<SAI1>: [gpioe::PE4: AF6,]
+    PinSdA<SAI1>: [gpiod::PD6: AF6, gpioe::PE6: AF6,]
+    PinMclkB<SAI1>: [gpiof::PF7: AF6,]
+    PinSckB<SAI1>: [gpiof::PF8: AF6,]
+    PinFsB<SAI1>: [gpiof::PF9: AF6,]
+    PinSdB<SAI1>: [gpioe::PE3: AF6, gpiof::PF6: AF6,]
+    PinMclkA<SAI2>: [gpioe::PE0: AF10, gpioi::PI4: AF10,]
+    PinSckA<SAI2>: [gpiod::PD13: AF10, gpioi::PI5: AF10,]
+    PinFsA<SAI2>: [gpiod::PD12: AF10, gpioi::PI7: AF10,]
+    PinSdA<SAI2>: [gpiod::PD11: AF10, gpioi::PI6: AF10,]
+    PinMclkB<SAI2>: [
+        gpioa::PA1: AF10, gpioe::PE6: AF10, gpioe::PE14: AF10, gpioh::PH3: AF10,
+    ]
+    PinSckB<SAI2>: [gpioa::PA2: AF8, gpioe::PE12: AF10, gpioh::PH2: AF10,]
+    PinFsB<SAI2>: [gpioa::PA12: AF8, gpioc::PC0: AF8, gpioe::PE13: AF10, gpiog::PG9: AF10,]
+    PinSdB<SAI2>: [gpioa::PA0: AF10, gpioe::PE11: AF10, gpiof::PF11: AF10, gpiog::PG10: AF10,]
+
+    PinSof<OTG_FS_GLOBAL>: [gpioa::PA8: AF10,]
+    PinId<OTG_FS_GLOBAL>: [gpioa::PA10: AF10,]
+    PinDm<OTG_FS_GLOBAL>: [gpioa::PA11: AF10,]
+    PinDp<OTG_FS_GLOBAL>: [gpioa::PA12: AF10,]
+}
+
+// The STM32F723/F733 USB OTG HS controller drives its integrated PHY instead of these pins
+#[cfg(not(feature = "stm32f7x3"))]
+pins! {
+    PinSof<OTG_HS_GLOBAL>: [gpioa::PA4: AF12,]
+    PinId<OTG_HS_GLOBAL>: [gpiob::PB12: AF12,]
+    PinDm<OTG_HS_GLOBAL>: [gpiob::PB14: AF12,]
+    PinDp<OTG_HS_GLOBAL>: [gpiob::PB15: AF12,]
+    PinClk<OTG_HS_GLOBAL>: [gpioa::PA5: AF10,]
+    PinD0<OTG_HS_GLOBAL>: [gpioa::PA3: AF10,]
+    PinD1<OTG_HS_GLOBAL>: [gpiob::PB0: AF10,]
+    PinD2<OTG_HS_GLOBAL>: [gpiob::PB1: AF10,]
+    PinD3<OTG_HS_GLOBAL>: [gpiob::PB10: AF10,]
+    PinD4<OTG_HS_GLOBAL>: [gpiob::PB11: AF10,]
+    PinD5<OTG_HS_GLOBAL>: [gpiob::PB12: AF10,]
+    PinD6<OTG_HS_GLOBAL>: [gpiob::PB13: AF10,]
+    PinD7<OTG_HS_GLOBAL>: [gpiob::PB5: AF10,]
+    PinStp<OTG_HS_GLOBAL>: [gpioc::PC0: AF10,]
+    PinDir<OTG_HS_GLOBAL>: [gpioc::PC2: AF10, gpioi::PI11: AF10,]
+    PinNxt<OTG_HS_GLOBAL>: [gpioc::PC3: AF10, gpioh::PH4: AF10,]
+}
+
+// Mappings of the STM32F72x/F73x and STM32F76x/F77x devices that the STM32F74x/F75x lack
+#[cfg(not(any(feature = "stm32f7x5", feature = "stm32f7x6")))]
+pins! {
+    PinTx<USART1>: [gpiob::PB14: AF4,]
+    PinRx<USART1>: [gpiob::PB15: AF4,]
+    PinTx<UART4>: [gpioa::PA12: AF6, gpiod::PD1: AF8, gpioh::PH13: AF8,]
+    PinRx<UART4>: [gpioa::PA11: AF6, gpiod::PD0: AF8, gpioh::PH14: AF8, gpioi::PI9: AF8,]
+    PinTx<UART5>: [gpiob::PB6: AF1, gpiob::PB9: AF7, gpiob::PB13: AF8,]
+    PinRx<UART5>: [gpiob::PB5: AF1, gpiob::PB8: AF7, gpiob::PB12: AF8,]
+    PinTx<UART7>: [gpioa::PA15: AF12, gpiob::PB4: AF12,]
+    PinRx<UART7>: [gpioa::PA8: AF12, gpiob::PB3: AF12,]
+
+    PinSck<SPI1>: [gpiog::PG11: AF5,]
+    PinMiso<SPI1>: [gpiog::PG9: AF5,]
+    PinMosi<SPI1>: [gpiod::PD7: AF5,]
+    PinNss<SPI1>: [gpiog::PG10: AF5,]
+    PinSck<SPI2>: [gpioa::PA12: AF5,]
+    PinNss<SPI2>: [gpioa::PA11: AF5,]
+
+    PinCts<UART4>: [gpiob::PB15: AF8,]
+    PinRts<UART4>: [gpiob::PB14: AF8,]
+
+    PinClk<SDMMC2>: [gpiod::PD6: AF11,]
+    PinCmd<SDMMC2>: [gpiod::PD7: AF11,]
+    PinD0<SDMMC2>: [gpiob::PB14: AF10, gpiog::PG9: AF11,]
+    PinD1<SDMMC2>: [gpiob::PB15: AF10, gpiog::PG10: AF11,]
+    PinD2<SDMMC2>: [gpiob::PB3: AF10, gpiog::PG11: AF10,]
+    PinD3<SDMMC2>: [gpiob::PB4: AF10, gpiog::PG12: AF11,]
+    PinD4<SDMMC2>: [gpiob::PB8: AF10,]
+    PinD5<SDMMC2>: [gpiob::PB9: AF10,]
+    PinD6<SDMMC2>: [gpioc::PC6: AF10,]
+    PinD7<SDMMC2>: [gpioc::PC7: AF10,]
+
+    PinBk1Ncs<QUADSPI>: [gpiob::PB10: AF9,]
+
+    PinNe1<FMC>: [gpioc::PC7: AF9,]
+    PinNe2<FMC>: [gpioc::PC8: AF9,]
+    PinNwait<FMC>: [gpioc::PC6: AF9,]
+    PinSdnwe<FMC>: [gpioa::PA7: AF12,]
+    PinSdne0<FMC>: [gpioc::PC4: AF12,]
+    PinSdcke0<FMC>: [gpioc::PC5: AF12,]
+}
+
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+pins! {
+    PinTx<CAN2>: [gpiob::PB6: AF9, gpiob::PB13: AF9,]
+    PinRx<CAN2>: [gpiob::PB5: AF9, gpiob::PB12: AF9,]
+
+    PinMdc<ETHERNET_MAC>: [gpioc::PC1: AF11,]
+    PinMdio<ETHERNET_MAC>: [gpioa::PA2: AF11,]
+    PinRefClk<ETHERNET_MAC>: [gpioa::PA1: AF11,]
+    PinTxClk<ETHERNET_MAC>: [gpioc::PC3: AF11,]
+    PinCrsDv<ETHERNET_MAC>: [gpioa::PA7: AF11,]
+    PinRxd0<ETHERNET_MAC>: [gpioc::PC4: AF11,]
+    PinRxd1<ETHERNET_MAC>: [gpioc::PC5: AF11,]
+    PinRxd2<ETHERNET_MAC>: [gpiob::PB0: AF11, gpioh::PH6: AF11,]
+    PinRxd3<ETHERNET_MAC>: [gpiob::PB1: AF11, gpioh::PH7: AF11,]
+    PinTxEn<ETHERNET_MAC>: [gpiob::PB11: AF11, gpiog::PG11: AF11,]
+    PinTxd0<ETHERNET_MAC>: [gpiob::PB12: AF11, gpiog::PG13: AF11,]
+    PinTxd1<ETHERNET_MAC>: [gpiob::PB13: AF11, gpiog::PG14: AF11,]
+    PinTxd2<ETHERNET_MAC>: [gpioc::PC2: AF11,]
+    PinTxd3<ETHERNET_MAC>: [gpiob::PB8: AF11, gpioe::PE2: AF11,]
+    PinRxEr<ETHERNET_MAC>: [gpiob::PB10: AF11, gpioi::PI10: AF11,]
+    PinCrs<ETHERNET_MAC>: [gpioa::PA0: AF11, gpioh::PH2: AF11,]
+    PinCol<ETHERNET_MAC>: [gpioa::PA3: AF11, gpioh::PH3: AF11,]
+    PinPpsOut<ETHERNET_MAC>: [gpiob::PB5: AF11, gpiog::PG8: AF11,]
+}
+
+// Mappings added with the STM32F76x/F77x devices
+#[cfg(any(feature = "stm32f7x7", feature = "stm32f7x9"))]
+pins! {
+    PinTx<CAN3>: [gpioa::PA15: AF11, gpiob::PB4: AF11,]
+    PinRx<CAN3>: [gpioa::PA8: AF11, gpiob::PB3: AF11,]
+}
+
+// The STM32F745 and STM32F765 have no LTDC
+#[cfg(any(feature = "stm32f7x6", feature = "stm32f7x7", feature = "stm32f7x9"))]
+pins! {
+    PinR0<LTDC>: [gpiog::PG13: AF14, gpioh::PH2: AF14, gpioi::PI15: AF14,]
+    PinR1<LTDC>: [gpioh::PH3: AF14, gpioj::PJ0: AF14,]
+    PinR2<LTDC>: [gpioa::PA1: AF14, gpioc::PC10: AF14, gpioh::PH8: AF14, gpioj::PJ1: AF14,]
+    PinR3<LTDC>: [gpiob::PB0: AF9, gpioh::PH9: AF14, gpioj::PJ2: AF14,]
+    PinR4<LTDC>: [gpioa::PA5: AF14, gpioa::PA11: AF14, gpioh::PH10: AF14, gpioj::PJ3: AF14,]
+    PinR5<LTDC>: [gpioa::PA12: AF14, gpioc::PC0: AF14, gpioh::PH11: AF14, gpioj::PJ4: AF14,]
+    PinR6<LTDC>: [gpioa::PA8: AF14, gpiob::PB1: AF9, gpioh::PH12: AF14, gpioj::PJ5: AF14,]
+    PinR7<LTDC>: [gpioe::PE15: AF14, gpiog::PG6: AF14, gpioj::PJ6: AF14,]
+    PinG0<LTDC>: [gpioe::PE5: AF14, gpioj::PJ7: AF14,]
+    PinG1<LTDC>: [gpioe::PE6: AF14, gpioj::PJ8: AF14,]
+    PinG2<LTDC>: [gpioa::PA6: AF14, gpioh::PH13: AF14, gpioj::PJ9: AF14,]
+    PinG3<LTDC>: [gpioe::PE11: AF14, gpiog::PG10: AF9, gpioh::PH14: AF14, gpioj::PJ10: AF14,]
+    PinG4<LTDC>: [gpiob::PB10: AF14, gpioh::PH15: AF14, gpioj::PJ11: AF14,]
+    PinG5<LTDC>: [gpiob::PB11: AF14, gpioi::PI0: AF14, gpiok::PK0: AF14,]
+    PinG6<LTDC>: [gpioc::PC7: AF14, gpioi::PI1: AF14, gpiok::PK1: AF14,]
+    PinG7<LTDC>: [gpiod::PD3: AF14, gpioi::PI2: AF14, gpiok::PK2: AF14,]
+    PinB0<LTDC>: [gpioe::PE4: AF14, gpiog::PG14: AF14, gpioj::PJ12: AF14,]
+    PinB1<LTDC>: [gpiog::PG12: AF14, gpioj::PJ13: AF14,]
+    PinB2<LTDC>: [gpiod::PD6: AF14, gpiog::PG10: AF14, gpioj::PJ14: AF14,]
+    PinB3<LTDC>: [gpiod::PD10: AF14, gpiog::PG11: AF14, gpioj::PJ15: AF14,]
+    PinB4<LTDC>: [gpioe::PE12: AF14, gpiog::PG12: AF9, gpioi::PI4: AF14, gpiok::PK3: AF14,]
+    PinB5<LTDC>: [gpioa::PA3: AF14, gpioi::PI5: AF14, gpiok::PK4: AF14,]
+    PinB6<LTDC>: [gpiob::PB8: AF14, gpioi::PI6: AF14, gpiok::PK5: AF14,]
+    PinB7<LTDC>: [gpiob::PB9: AF14, gpioi::PI7: AF14, gpiok::PK6: AF14,]
+    PinClk<LTDC>: [gpioe::PE14: AF14, gpiog::PG7: AF14, gpioi::PI14: AF14,]
+    PinHsync<LTDC>: [gpioc::PC6: AF14, gpioi::PI10: AF14, gpioi::PI12: AF14,]
+    PinVsync<LTDC>: [gpioa::PA4: AF14, gpioi::PI9: AF14, gpioi::PI13: AF14,]
+    PinDe<LTDC>: [gpioe::PE13: AF14, gpiof::PF10: AF14, gpiok::PK7: AF14,]
+}
+
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+pins! {
+    PinSck<SPI6>: [gpioa::PA5: AF8, gpiob::PB3: AF8, gpiog::PG13: AF5,]
+    PinMiso<SPI6>: [gpioa::PA6: AF8, gpiob::PB4: AF8, gpiog::PG12: AF5,]
+    PinMosi<SPI6>: [gpioa::PA7: AF8, gpiob::PB5: AF8, gpiog::PG14: AF5,]
+    PinNss<SPI6>: [gpioa::PA4: AF8, gpioa::PA15: AF7, gpiog::PG8: AF5,]
+}
+
+open_drain_pins! {
+    PinScl<I2C1>: [gpiob::PB6: AF4, gpiob::PB8: AF4,]
+    PinSda<I2C1>: [gpiob::PB7: AF4, gpiob::PB9: AF4,]
+    PinScl<I2C2>: [gpiob::PB10: AF4, gpiof::PF1: AF4, gpioh::PH4: AF4,]
+    PinSda<I2C2>: [gpiob::PB11: AF4, gpiof::PF0: AF4, gpioh::PH5: AF4,]
+    PinScl<I2C3>: [gpioa::PA8: AF4, gpioh::PH7: AF4,]
+    PinSda<I2C3>: [gpioc::PC9: AF4, gpioh::PH8: AF4,]
+}
+
+#[cfg(not(any(feature = "stm32f7x2", feature = "stm32f7x3")))]
+open_drain_pins! {
+    PinScl<I2C4>: [gpiod::PD12: AF4, gpiof::PF14: AF4, gpioh::PH11: AF4,]
+    PinSda<I2C4>: [gpiod::PD13: AF4, gpiof::PF15: AF4, gpioh::PH12: AF4,]
+}
+
+// Mappings added with the STM32F76x/F77x devices
+#[cfg(any(feature = "stm32f7x7", feature = "stm32f7x9"))]
+open_drain_pins! {
+    PinScl<I2C4>: [gpiob::PB6: AF11, gpiob::PB8: AF1,]
+    PinSda<I2C4>: [gpiob::PB7: AF11, gpiob::PB9: AF1,]
+}
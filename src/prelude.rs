@@ -2,6 +2,12 @@
 
 pub use crate::hal::prelude::*; // embedded-hal traits
 
+pub use crate::hal::digital::v2::InputPin as _embedded_hal_digital_v2_InputPin;
+pub use crate::hal::digital::v2::OutputPin as _embedded_hal_digital_v2_OutputPin;
+pub use crate::hal::digital::v2::StatefulOutputPin as _embedded_hal_digital_v2_StatefulOutputPin;
+pub use crate::hal::digital::v2::ToggleableOutputPin as _embedded_hal_digital_v2_ToggleableOutputPin;
+
 pub use crate::flash::FlashExt as _stm32f7_hal_FlashExt;
 pub use crate::gpio::GpioExt as _stm32f7_hal_GpioExt;
 pub use crate::rcc::RccExt as _stm32f7_hal_RccExt;
+pub use crate::syscfg::SysCfgExt as _stm32f7_hal_SysCfgExt;
@@ -4,6 +4,9 @@ use crate::flash::ACR;
 use crate::stm32::{rcc, RCC};
 use crate::time::Hertz;
 
+/// HSI oscillator frequency
+const HSI: u32 = 16_000_000;
+
 /// Extension trait that constrains the `RCC` peripheral
 pub trait RccExt {
     /// Constrains the `RCC` peripheral so it plays nicely with the other abstractions
@@ -219,7 +222,8 @@ impl CFGR {
         unsafe { &(*RCC::ptr()).cfgr }
     }
 
-    pub fn freeze(&mut self, acr: &mut ACR) {
+    /// Freezes the clock configuration, making it effective
+    pub fn freeze(&mut self, acr: &mut ACR) -> Clocks {
         let rcc = unsafe { &*RCC::ptr() };
 
         // TODO: Implement lock configuration
@@ -246,6 +250,14 @@ impl CFGR {
                 .bits(sysclk_src_bits)
         });
         while rcc.cfgr.read().sws().bits() != sysclk_src_bits {}
+
+        let sysclk = Hertz(HSI);
+        Clocks {
+            hclk: sysclk,
+            pclk1: sysclk,
+            pclk2: sysclk,
+            sysclk,
+        }
     }
 }
 
@@ -366,7 +378,35 @@ impl SSCGR {
     }
 }
 
+/// Frozen clock frequencies
+///
+/// The existence of this value indicates that the clock configuration can no longer be changed
 #[derive(Clone, Copy, Debug)]
 pub struct Clocks {
     hclk: Hertz,
+    pclk1: Hertz,
+    pclk2: Hertz,
+    sysclk: Hertz,
+}
+
+impl Clocks {
+    /// Returns the frequency of the AHB bus and of the core
+    pub fn hclk(&self) -> Hertz {
+        self.hclk
+    }
+
+    /// Returns the frequency of the APB1 bus
+    pub fn pclk1(&self) -> Hertz {
+        self.pclk1
+    }
+
+    /// Returns the frequency of the APB2 bus
+    pub fn pclk2(&self) -> Hertz {
+        self.pclk2
+    }
+
+    /// Returns the system clock frequency
+    pub fn sysclk(&self) -> Hertz {
+        self.sysclk
+    }
 }
@@ -0,0 +1,84 @@
+//! System configuration controller
+
+use core::ptr;
+
+use crate::rcc::APB2;
+use crate::stm32::{syscfg, SYSCFG};
+
+const CMPCR_CMP_PD: u32 = 1 << 0;
+const CMPCR_READY: u32 = 1 << 8;
+
+/// Extension trait that constrains the `SYSCFG` peripheral
+pub trait SysCfgExt {
+    /// Constrains the `SYSCFG` peripheral so it plays nicely with the other abstractions
+    fn constrain(self, apb2: &mut APB2) -> SysCfg;
+}
+
+impl SysCfgExt for SYSCFG {
+    fn constrain(self, apb2: &mut APB2) -> SysCfg {
+        apb2.enr().modify(|_, w| w.syscfgen().set_bit());
+
+        SysCfg {
+            compensation_cell: false,
+        }
+    }
+}
+
+/// Constrained SYSCFG peripheral
+pub struct SysCfg {
+    /// A `CompensationCell` token is alive
+    compensation_cell: bool,
+}
+
+impl SysCfg {
+    pub(crate) fn regs(&mut self) -> &syscfg::RegisterBlock {
+        // NOTE(unsafe) this proxy grants exclusive access to the peripheral
+        unsafe { &*SYSCFG::ptr() }
+    }
+
+    /// Enables the I/O compensation cell and waits until it is ready
+    ///
+    /// The cell reduces the slew rate noise of the pins and is required for pins switching
+    /// faster than 50 MHz, i.e. configured with `set_very_high_speed`. Returns `None` if the cell
+    /// has already been enabled, as there is only one token.
+    pub fn enable_compensation_cell(&mut self) -> Option<CompensationCell> {
+        if self.compensation_cell {
+            return None;
+        }
+
+        let cmpcr = self.cmpcr();
+        // NOTE(unsafe) this proxy grants exclusive access to the peripheral
+        unsafe { ptr::write_volatile(cmpcr, ptr::read_volatile(cmpcr) | CMPCR_CMP_PD) }
+
+        while !compensation_cell_ready() {}
+        self.compensation_cell = true;
+
+        Some(CompensationCell { _0: () })
+    }
+
+    /// Powers the I/O compensation cell down
+    pub fn disable_compensation_cell(&mut self, _cell: CompensationCell) {
+        let cmpcr = self.cmpcr();
+        // NOTE(unsafe) this proxy grants exclusive access to the peripheral
+        unsafe { ptr::write_volatile(cmpcr, ptr::read_volatile(cmpcr) & !CMPCR_CMP_PD) }
+        self.compensation_cell = false;
+    }
+
+    /// Returns a pointer to CMPCR, which the SVD marks as read-only although CMP_PD is writable
+    fn cmpcr(&mut self) -> *mut u32 {
+        // NOTE(unsafe) no reference to the register is created
+        unsafe { ptr::addr_of!((*SYSCFG::ptr()).cmpcr) as *mut u32 }
+    }
+}
+
+/// Proof that the I/O compensation cell is enabled and ready
+pub struct CompensationCell {
+    _0: (),
+}
+
+/// Returns `true` if the I/O compensation cell is enabled and ready
+fn compensation_cell_ready() -> bool {
+    // NOTE(unsafe) atomic read with no side effects
+    let cmpcr = unsafe { (*SYSCFG::ptr()).cmpcr.read().bits() };
+    cmpcr & (CMPCR_CMP_PD | CMPCR_READY) == CMPCR_CMP_PD | CMPCR_READY
+}
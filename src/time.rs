@@ -1,4 +1,11 @@
-//! Time units
+//! Time units and cycle accurate time measurements
+
+use cortex_m::peripheral::{DCB, DWT};
+
+use crate::rcc::Clocks;
+
+/// Key unlocking the DWT registers on the Cortex-M7
+const LAR_KEY: u32 = 0xC5AC_CE55;
 
 /// Hertz
 #[derive(Clone, Copy, Debug)]
@@ -17,3 +24,143 @@ impl U32Ext for u32 {
         Hertz(self)
     }
 }
+
+/// Monotonic timer based on the DWT cycle counter
+///
+/// The counter runs at the core clock (HCLK) frequency and wraps around every 2^32 cycles (about 20
+/// seconds at 216 MHz), so only shorter durations can be measured.
+#[derive(Clone, Copy)]
+pub struct MonoTimer {
+    frequency: Hertz,
+}
+
+impl MonoTimer {
+    /// Enables the cycle counter and creates the timer
+    ///
+    /// The DWT is consumed so the cycle counter can no longer be stopped or reset.
+    pub fn new(mut dwt: DWT, dcb: &mut DCB, clocks: Clocks) -> Self {
+        dcb.enable_trace();
+        // NOTE(unsafe) the Cortex-M7 ignores writes to the DWT registers until it is unlocked
+        unsafe { dwt.lar.write(LAR_KEY) }
+        dwt.enable_cycle_counter();
+
+        MonoTimer {
+            frequency: clocks.hclk(),
+        }
+    }
+
+    /// Returns the frequency at which the timer counts
+    pub fn frequency(&self) -> Hertz {
+        self.frequency
+    }
+
+    /// Returns the current instant
+    pub fn now(&self) -> Instant {
+        Instant {
+            now: DWT::get_cycle_count(),
+            frequency: self.frequency.0,
+        }
+    }
+
+    /// Starts a stopwatch
+    pub fn stopwatch(&self) -> Stopwatch {
+        let now = self.now();
+        Stopwatch {
+            start: now,
+            lap: now,
+        }
+    }
+}
+
+/// Point in time measured by a `MonoTimer`
+#[derive(Clone, Copy, Debug)]
+pub struct Instant {
+    now: u32,
+    frequency: u32,
+}
+
+impl Instant {
+    /// Returns the time elapsed since this instant
+    pub fn elapsed(&self) -> Duration {
+        Duration {
+            ticks: DWT::get_cycle_count().wrapping_sub(self.now),
+            frequency: self.frequency,
+        }
+    }
+
+    /// Returns the time elapsed from `earlier` to this instant
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        Duration {
+            ticks: self.now.wrapping_sub(earlier.now),
+            frequency: self.frequency,
+        }
+    }
+}
+
+/// Span of time measured by a `MonoTimer`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration {
+    ticks: u32,
+    frequency: u32,
+}
+
+impl Duration {
+    /// Returns the duration in timer ticks, i.e. core clock cycles
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Returns the duration in nanoseconds
+    pub fn as_nanos(&self) -> u64 {
+        u64::from(self.ticks) * 1_000_000_000 / u64::from(self.frequency)
+    }
+
+    /// Returns the duration in microseconds
+    pub fn as_micros(&self) -> u64 {
+        u64::from(self.ticks) * 1_000_000 / u64::from(self.frequency)
+    }
+
+    /// Returns the duration in milliseconds
+    pub fn as_millis(&self) -> u64 {
+        u64::from(self.ticks) * 1_000 / u64::from(self.frequency)
+    }
+}
+
+/// Stopwatch for profiling code sections
+pub struct Stopwatch {
+    start: Instant,
+    lap: Instant,
+}
+
+impl Stopwatch {
+    /// Restarts the stopwatch
+    pub fn reset(&mut self) {
+        self.start.now = DWT::get_cycle_count();
+        self.lap = self.start;
+    }
+
+    /// Returns the time elapsed since the previous lap, or the start, and starts a new lap
+    pub fn lap(&mut self) -> Duration {
+        let now = Instant {
+            now: DWT::get_cycle_count(),
+            frequency: self.lap.frequency,
+        };
+        let lap = now.duration_since(self.lap);
+        self.lap = now;
+
+        lap
+    }
+
+    /// Returns the time elapsed since the start
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Runs `f` and returns its result along with the time it took
+    pub fn measure<R>(&mut self, f: impl FnOnce() -> R) -> (R, Duration) {
+        self.lap();
+        let r = f();
+
+        (r, self.lap())
+    }
+}